@@ -1,22 +1,46 @@
 use wasm_bindgen::prelude::*;
 use serde::{Serialize, Deserialize};
 use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
 use regex::Regex;
 use rand_chacha::ChaCha8Rng;
 use rand_chacha::rand_core::SeedableRng;
 use rand::seq::SliceRandom;
 use rand::Rng;
 use unidecode::unidecode;
+use unicode_normalization::UnicodeNormalization;
 
 // Game constants
 const CARD_WIDTH: f64 = 150.0;
+// Cards grow with their front text up to this width
+const MAX_CARD_WIDTH: f64 = 300.0;
+// Rough width of one character of card text plus the card's side padding
+const CARD_CHAR_WIDTH: f64 = 10.0;
+const CARD_TEXT_PADDING: f64 = 20.0;
 const CARD_HEIGHT: f64 = 50.0;
+// Candidate x positions tried when other cards are falling
+const SPAWN_POSITION_CANDIDATES: usize = 8;
+// Cards spawned together in a burst, see `set_burst_probability`
+const MIN_BURST_SIZE: usize = 2;
+const MAX_BURST_SIZE: usize = 3;
+// Deck entries looked at when the next one is already falling
+const MAX_SPAWN_REDRAWS: usize = 5;
 
 // Deck and card unlocking constants
 const INITIAL_UNLOCKED_CARDS: usize = 10;
 const SCORE_PER_CARD_UNLOCK: i32 = 10;
 const CARDS_PER_UNLOCK: usize = 5;
 const DECK_CARD_DUPLICATES: u32 = 3;
+const MAX_LEITNER_BOX: u32 = 4;
+// Extra deck copies per miss beyond a card's successes. 0 disables the weighting.
+const MISS_WEIGHT: f64 = 1.0;
+const MAX_MISS_EXTRA_DUPLICATES: u32 = 6;
+
+// Spaced repetition scheduling constants (SM-2 style, measured in decks)
+const INITIAL_SCHEDULE_EASE: f64 = 2.5;
+const MIN_SCHEDULE_EASE: f64 = 1.3;
+const SCHEDULE_EASE_BONUS: f64 = 0.1;
+const SCHEDULE_EASE_PENALTY: f64 = 0.2;
 
 // Difficulty scaling constants
 const INITIAL_MAX_CARDS: usize = 1;
@@ -28,11 +52,50 @@ const SPAWN_INTERVAL_DECREASE: f64 = 0.25;
 const INITIAL_CARD_SPEED: f64 = 50.0;
 const CARD_SPEED_INCREASE_PER_SCORE: f64 = 2.0;
 
+// 64-bit FNV-1a parameters used for `score_signature`
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// Version of the `to_save_state` JSON format
+const SAVE_STATE_VERSION: u32 = 1;
+
 // Health and scoring constants
+const INITIAL_HEALTH: i32 = 3;
+const MAX_HEALTH: i32 = 5;
 const SCORE_PER_HEART: i32 = 5;
+const FREE_MISSES_FOR_NEW_CARDS: u32 = 2;
+const INITIAL_SKIPS: u32 = 3;
+const COMBO_STREAK_PER_MULTIPLIER: u32 = 3;
+const MAX_COMBO_MULTIPLIER: u32 = 3;
+// The combo is lost if no card is cleared for this long
+const COMBO_WINDOW_SECONDS: f64 = 5.0;
+
+// Lowest accepted `set_speed_multiplier` value
+const MIN_SPEED_MULTIPLIER: f64 = 0.1;
+
+// Share of the normal card speed while slow motion is active
+const SLOWMO_SPEED_FACTOR: f64 = 0.5;
+// Every this many consecutive correct answers grant slow motion for a while
+const SLOWMO_COMBO_THRESHOLD: u32 = 10;
+const SLOWMO_COMBO_SECONDS: f64 = 5.0;
+
+// Score bucket for cards without a category
+const DEFAULT_CATEGORY: &str = "default";
+// Points for clearing a card whose deck entry sets none
+const DEFAULT_CARD_POINTS: u32 = 1;
+
+// How long after a miss `undo_last_miss` is still accepted
+const UNDO_MISS_WINDOW_SECONDS: f64 = 2.0;
+
+// How long a missed card stays on screen showing its answer
+const FLIP_DISPLAY_SECONDS: f64 = 1.0;
+const MIN_FLIP_DISPLAY_SECONDS: f64 = 0.1;
+
+// How long a card must have been falling before `request_hint` reveals it
+const HINT_DELAY_SECONDS: f64 = 2.0;
 
 #[wasm_bindgen]
-#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
 pub enum GameMode {
     #[default]
     Normal,
@@ -40,7 +103,83 @@ pub enum GameMode {
     Both,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+// Bundles of baseline values for the spawn interval, speed, health, free
+// misses and how fast the game scales with the score. Explicit `GameConfig`
+// values still take precedence.
+#[wasm_bindgen]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    fn initial_spawn_interval(self) -> f64 {
+        match self {
+            Difficulty::Easy => 4.0,
+            Difficulty::Normal => INITIAL_SPAWN_INTERVAL,
+            Difficulty::Hard => 2.0,
+            Difficulty::Expert => 1.5,
+        }
+    }
+
+    fn initial_card_speed(self) -> f64 {
+        match self {
+            Difficulty::Easy => 35.0,
+            Difficulty::Normal => INITIAL_CARD_SPEED,
+            Difficulty::Hard => 70.0,
+            Difficulty::Expert => 90.0,
+        }
+    }
+
+    fn initial_health(self) -> i32 {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal => INITIAL_HEALTH,
+            Difficulty::Hard => 2,
+            Difficulty::Expert => 1,
+        }
+    }
+
+    fn max_health(self) -> i32 {
+        match self {
+            Difficulty::Easy => 7,
+            Difficulty::Normal => MAX_HEALTH,
+            Difficulty::Hard => 4,
+            Difficulty::Expert => 3,
+        }
+    }
+
+    fn score_per_max_card_increase(self) -> i32 {
+        match self {
+            Difficulty::Easy => 15,
+            Difficulty::Normal | Difficulty::Hard => SCORE_PER_MAX_CARD_INCREASE,
+            Difficulty::Expert => 5,
+        }
+    }
+
+    fn score_per_spawn_interval_decrease(self) -> i32 {
+        match self {
+            Difficulty::Easy => 8,
+            Difficulty::Normal | Difficulty::Hard => SCORE_PER_SPAWN_INTERVAL_DECREASE,
+            Difficulty::Expert => 3,
+        }
+    }
+
+    fn free_misses_for_new_cards(self) -> u32 {
+        match self {
+            Difficulty::Easy => 3,
+            Difficulty::Normal => FREE_MISSES_FOR_NEW_CARDS,
+            Difficulty::Hard => 1,
+            Difficulty::Expert => 0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Card {
     pub id: u32,
     pub raw_front: String,
@@ -52,19 +191,51 @@ pub struct Card {
     pub flipped: bool,
     pub time_since_flipped: Option<f64>,
     pub free_misses: u32,
+    pub hint: Option<String>,
+    // Seconds since the card was spawned
+    #[serde(default)]
+    pub age: f64,
+    // Sized to the front text when spawned
+    #[serde(default)]
+    pub width: f64,
+    // Indices of the back's alternatives submitted so far, used when every
+    // alternative is required
+    #[serde(default)]
+    pub answered_alternatives: Vec<usize>,
+    #[serde(default)]
+    pub category: Option<String>,
+    // Points for clearing the card, `DEFAULT_CARD_POINTS` if unset
+    #[serde(default)]
+    pub points: Option<u32>,
+    // Seconds left showing the back of a never-seen card as a preview
+    #[serde(default)]
+    pub preview_until: Option<f64>,
 }
 
+// Fields missing from older save states take their `Default` values.
 #[wasm_bindgen]
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct Game {
     cards: Vec<Card>,
     missed_cards: Vec<Card>,
-    card_deck: Vec<(String, String)>,
+    card_deck: Vec<CustomCard>,
     unlocked_cards_count: usize,
     card_miss_counts: HashMap<String, u32>,
     card_success_counts: HashMap<String, u32>,
+    // Leitner box per raw_front: promoted on success, back to 0 on a miss
+    card_boxes: HashMap<String, u32>,
+    card_schedules: HashMap<String, CardSchedule>,
+    // Summed spawn-to-clear seconds and clear count per raw_front
+    card_latencies: HashMap<String, (f64, u32)>,
+    // Number of decks built so far; schedule intervals are counted in decks
+    deck_round: u32,
     width: f64,
     height: f64,
     score: i32,
+    // Points before the combo multiplier. Unlocks follow this, so combos
+    // don't unlock cards faster.
+    raw_score: i32,
     time_since_last_card: f64,
     card_spawn_interval: f64,
     card_speed: f64,
@@ -74,23 +245,217 @@ pub struct Game {
     game_over: bool,
     paused: bool,
     rng_seed: u64,
+    // Restored from the seed and word position stored next to the game state
+    #[serde(skip, default = "default_rng")]
     rng: ChaCha8Rng,
     game_id: u32,
     mode: GameMode,
     next_card_id: u32,
     speed_multiplier: f64,
-    card_data: Vec<(String, String)>,
+    card_data: Vec<CustomCard>,
+    fuzzy_tolerance: u32,
+    config: GameConfig,
+    skips_remaining: u32,
+    time_remaining: Option<f64>,
+    combo_streak: u32,
+    time_since_last_correct: f64,
+    // None until the first heart is lost
+    time_since_last_damage: Option<f64>,
+    last_miss: Option<LastMiss>,
+    // Sum and number of spawn-to-clear times of cleared cards
+    total_response_time: f64,
+    response_count: u32,
+    // Seconds of unpaused play before game over, also timestamps the replay log
+    elapsed: f64,
+    replay_log: Vec<ReplayEntry>,
+    // Config and speed multiplier when the replay log started; later changes
+    // are part of the log
+    replay_config: GameConfig,
+    replay_speed_multiplier: f64,
+    // Answers from `queue_answer`, resolved in order at the next tick
+    queued_answers: Vec<String>,
+    // Points scored this game per card category
+    category_scores: HashMap<String, i32>,
+    // Seconds of slow motion left, 0 when inactive
+    slowmo_remaining: f64,
+    // Ids of falling cards whose answer was partially revealed
+    hints_used: HashSet<u32>,
+    // Raw fronts the player took out of rotation. Kept across restarts.
+    excluded: HashSet<String>,
+    // FNV-1a hash of the raw fronts of cleared cards in clearing order
+    answer_digest: u64,
+    // Consecutive correct answers, unlike the combo not tied to scoring
+    current_streak: u32,
+    best_streak: u32,
+    #[serde(skip)]
+    events: Vec<GameEvent>,
+}
+
+// Per-card spaced repetition state. A card is due once the deck round reaches
+// `due_round`; each success stretches the interval by the ease factor and a
+// miss makes the card due again immediately.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct CardSchedule {
+    interval: u32,
+    ease: f64,
+    due_round: u32,
+}
+
+impl Default for CardSchedule {
+    fn default() -> Self {
+        Self { interval: 0, ease: INITIAL_SCHEDULE_EASE, due_round: 0 }
+    }
+}
+
+impl CardSchedule {
+    fn record_success(&mut self, round: u32) {
+        self.interval = match self.interval {
+            0 => 1,
+            1 => 2,
+            interval => (interval as f64 * self.ease).round() as u32,
+        };
+        self.ease += SCHEDULE_EASE_BONUS;
+        self.due_round = round.saturating_add(self.interval);
+    }
+
+    fn record_miss(&mut self, round: u32) {
+        self.interval = 0;
+        self.ease = (self.ease - SCHEDULE_EASE_PENALTY).max(MIN_SCHEDULE_EASE);
+        self.due_round = round;
+    }
+}
+
+// Continues a 64-bit FNV-1a hash over `bytes`. Start from `FNV_OFFSET_BASIS`.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME))
+}
+
+fn default_rng() -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(0)
+}
+
+// Envelope of a saved game. The RNG stream position is stored separately so
+// that a loaded game continues with exactly the same random draws.
+#[derive(Serialize, Deserialize)]
+struct SaveState<G> {
+    version: u32,
+    rng_word_pos: u128,
+    game: G,
+}
+
+// How answers and card backs are normalized before comparison.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+enum TextNormalization {
+    // Transliterate to ASCII, so "crème" matches "creme".
+    #[default]
+    Latin,
+    // NFC normalization only, keeping non-Latin scripts such as CJK or Arabic intact.
+    Unicode,
+}
+
+// Game-level settings for `normalize_with`, taken from the `GameConfig`.
+#[derive(Clone, Copy, Default)]
+struct NormalizeOptions<'a> {
+    mode: TextNormalization,
+    case_sensitive: bool,
+    // Sort the words, so answers match regardless of word order
+    ignore_word_order: bool,
+    // Words dropped before comparing, unless nothing else is left
+    stopwords: &'a [String],
 }
 
 fn normalize_string(s: &str) -> String {
-    unidecode(s)
-        .to_lowercase()
+    normalize_with(s, NormalizeOptions::default())
+}
+
+/// Normalizes an answer exactly like a default game does before comparing
+/// it, so the frontend can preview it.
+#[wasm_bindgen]
+pub fn normalize_answer(s: &str) -> String {
+    normalize_string(s)
+}
+
+// Answers and card backs go through the same steps, so punctuation anywhere
+// (a trailing period, an apostrophe, a comma) is dropped on both sides alike:
+// "hello." matches "hello" and "don't" matches "dont".
+fn normalize_with(s: &str, options: NormalizeOptions) -> String {
+    let text: String = match options.mode {
+        TextNormalization::Latin => unidecode(s),
+        TextNormalization::Unicode => s.nfc().collect(),
+    };
+    let text = if options.case_sensitive { text } else { text.to_lowercase() };
+    let text: String = text
         .chars()
-        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
-        .collect::<String>()
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
+        .filter(|&c| match options.mode {
+            TextNormalization::Latin => c.is_alphanumeric() || c.is_whitespace(),
+            // Combining marks are not alphanumeric, so only punctuation is dropped.
+            TextNormalization::Unicode => c.is_whitespace() || !is_punctuation(c),
+        })
+        .collect();
+    let mut words: Vec<&str> = text.split_whitespace().collect();
+    if !options.stopwords.is_empty() {
+        let stopwords: Vec<String> = options
+            .stopwords
+            .iter()
+            .map(|word| normalize_with(word, NormalizeOptions { stopwords: &[], ..options }))
+            .collect();
+        let key_words: Vec<&str> = words.iter().copied().filter(|word| !stopwords.iter().any(|s| s == word)).collect();
+        if !key_words.is_empty() {
+            words = key_words;
+        }
+    }
+    if options.ignore_word_order {
+        words.sort_unstable();
+    }
+    words.join(" ")
+}
+
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation()
+        || matches!(c,
+            '\u{00A1}'..='\u{00BF}'
+            | '\u{060C}' | '\u{061B}' | '\u{061F}'
+            | '\u{2000}'..='\u{206F}'
+            | '\u{3000}'..='\u{303F}'
+            | '\u{FF01}'..='\u{FF0F}'
+            | '\u{FF1A}'..='\u{FF20}')
+}
+
+// Levenshtein edit distance between two strings, counted in characters.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b_chars.len() + 1];
+        for (j, cb) in b_chars.iter().enumerate() {
+            let substitution_cost = if ca == *cb { 0 } else { 1 };
+            current[j + 1] = (previous[j] + substitution_cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b_chars.len()]
+}
+
+// Caps the edit tolerance so that more than half of the target has to be typed
+// correctly. Without this, a 1-2 character answer would match almost anything.
+fn effective_tolerance(target: &str, tolerance: u32) -> usize {
+    let max_for_length = target.chars().count().saturating_sub(1) / 2;
+    (tolerance as usize).min(max_for_length)
+}
+
+// Checks a single answer alternative against an already normalized answer.
+// A tolerance of 0 means exact matching.
+fn answer_matches(alternative: &str, normalized_answer: &str, tolerance: u32, options: NormalizeOptions) -> bool {
+    let normalized_alternative = normalize_with(alternative.trim(), options);
+    if tolerance == 0 {
+        normalized_alternative == normalized_answer
+    } else {
+        let max_edits = effective_tolerance(&normalized_alternative, tolerance);
+        levenshtein(&normalized_alternative, normalized_answer) <= max_edits
+    }
 }
 
 #[derive(Serialize)]
@@ -99,15 +464,243 @@ struct CardForDisplay<'a> {
     raw_back: &'a str,
     front: &'a str,
     back: &'a str,
+    tags: &'a [String],
     success_count: u32,
     miss_count: u32,
     is_unlocked: bool,
+    is_excluded: bool,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+// A card as read from a deck file. Text decks hold front, back, hint and
+// tags in that column order; a `#tags column:N` header moves the tags (and
+// the category and points columns after them) and frees the hint column.
+#[derive(Serialize, Deserialize, Clone, Default)]
 struct CustomCard {
     front: String,
     back: String,
+    #[serde(default)]
+    hint: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    category: Option<String>,
+    #[serde(default)]
+    points: Option<u32>,
+}
+
+// Optional tuning overrides passed to `Game::new`. Every absent field falls
+// back to the corresponding module constant.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct GameConfig {
+    difficulty: Difficulty,
+    initial_health: Option<i32>,
+    max_health: Option<i32>,
+    initial_spawn_interval: Option<f64>,
+    min_spawn_interval: Option<f64>,
+    initial_card_speed: Option<f64>,
+    score_per_max_card_increase: Option<i32>,
+    // Ceiling on simultaneous falling cards. Absent means no limit.
+    max_cards_cap: Option<usize>,
+    score_per_spawn_interval_decrease: Option<i32>,
+    score_per_heart: Option<i32>,
+    // Misses a never-seen card may take before costing health, reduced by one
+    // for every earlier answer or miss. Also accepted as `new_card_free_misses`.
+    #[serde(alias = "new_card_free_misses")]
+    free_misses_for_new_cards: Option<u32>,
+    // Also accepted as `flipped_card_lifetime`.
+    #[serde(alias = "flipped_card_lifetime")]
+    flip_display_seconds: Option<f64>,
+    // Never-seen cards show their back for this many seconds after spawning.
+    // 0 (the default) disables the preview.
+    answer_preview_seconds: f64,
+    // Size of a card with a short front. Absent means `CARD_WIDTH` and
+    // `CARD_HEIGHT`. The height also decides where cards flip.
+    card_width: Option<f64>,
+    card_height: Option<f64>,
+    // Bounds for card widths, which follow the length of the front text.
+    // Absent means `card_width` and `MAX_CARD_WIDTH`.
+    min_card_width: Option<f64>,
+    max_card_width: Option<f64>,
+    // Cards spawn in this many evenly spaced columns. Absent means anywhere
+    // across the width.
+    spawn_columns: Option<usize>,
+    // Blitz mode: a card at the bottom falls `1 + blitz_acceleration` times as
+    // fast as one at the top, growing linearly in between. 0 (the default)
+    // keeps every card at the same speed.
+    blitz_acceleration: f64,
+    // Space in pixels kept free below the flip line, e.g. for an on-screen
+    // keyboard. 0 (the default) flips cards at the very bottom.
+    bottom_margin: f64,
+    // Chance that a spawn brings extra cards along. 0 (the default) disables bursts.
+    burst_probability: f64,
+    // After losing a heart, further misses within this many seconds cost no
+    // health. 0 (the default) disables the grace window.
+    damage_cooldown_seconds: f64,
+    // Length of a timed game in seconds. Absent means the game only ends when
+    // health runs out. Also accepted as `time_limit_seconds`.
+    #[serde(alias = "time_limit_seconds")]
+    time_limit: Option<f64>,
+    // Combo length that triggers slow motion. Absent means
+    // `SLOWMO_COMBO_THRESHOLD`, 0 disables it.
+    slowmo_combo_threshold: Option<u32>,
+    // Spawn interval and card speed stay at their starting values whatever the
+    // score. Also accepted as `endless` and `static_difficulty`.
+    #[serde(alias = "endless", alias = "static_difficulty")]
+    constant_difficulty: bool,
+    // Set to false to start with an empty board, so the first card only
+    // spawns on the first tick. Absent means true.
+    autostart: Option<bool>,
+    // Any card reaching the bottom ends the game, ignoring health and free misses.
+    hardcore: bool,
+    // Missed cards are still counted, but never cost health or end the game.
+    // Also accepted as `zen`.
+    #[serde(alias = "zen")]
+    practice: bool,
+    normalization: TextNormalization,
+    // Set to false where accents matter, e.g. so "a" does not match "á". This
+    // keeps accents like `TextNormalization::Unicode` does. Absent means true.
+    strip_diacritics: Option<bool>,
+    // Only cards with at least one of these tags are played. Empty means all cards.
+    tag_filter: Vec<String>,
+    // Keep letter case when comparing answers, e.g. for German nouns.
+    case_sensitive: bool,
+    // Cards only clear once every alternative on the back has been submitted,
+    // for cloze-style decks listing several required answers.
+    require_all_answers: bool,
+    // Accept the words of an alternative in any order.
+    ignore_word_order: bool,
+    // Function words such as "the" that answers may add or leave out.
+    stopwords: Vec<String>,
+    deck: DeckConfig,
+}
+
+// Optional overrides for how quickly cards are unlocked and how often they are
+// repeated in the deck.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct DeckConfig {
+    initial_unlocked_cards: Option<usize>,
+    score_per_card_unlock: Option<i32>,
+    cards_per_unlock: Option<usize>,
+    deck_card_duplicates: Option<u32>,
+    miss_weight: Option<f64>,
+    // Every card is available from the start instead of being unlocked by score.
+    unlock_all: bool,
+}
+
+impl DeckConfig {
+    fn initial_unlocked_cards(&self) -> usize {
+        self.initial_unlocked_cards.unwrap_or(INITIAL_UNLOCKED_CARDS)
+    }
+
+    fn score_per_card_unlock(&self) -> i32 {
+        self.score_per_card_unlock.unwrap_or(SCORE_PER_CARD_UNLOCK).max(1)
+    }
+
+    fn cards_per_unlock(&self) -> usize {
+        self.cards_per_unlock.unwrap_or(CARDS_PER_UNLOCK)
+    }
+
+    fn deck_card_duplicates(&self) -> u32 {
+        self.deck_card_duplicates.unwrap_or(DECK_CARD_DUPLICATES).max(1)
+    }
+
+    fn miss_weight(&self) -> f64 {
+        self.miss_weight.unwrap_or(MISS_WEIGHT).max(0.0)
+    }
+}
+
+impl GameConfig {
+    // Rejects settings a game cannot start with.
+    fn check(&self) -> Result<(), String> {
+        if self.initial_health() < 1 || self.max_health() < 1 {
+            return Err("Health must be at least 1.".to_string());
+        }
+        if self.initial_health() > self.max_health() {
+            return Err(format!(
+                "Initial health {} cannot exceed max health {}.",
+                self.initial_health(),
+                self.max_health()
+            ));
+        }
+        Ok(())
+    }
+
+    fn initial_health(&self) -> i32 {
+        self.initial_health.unwrap_or(self.difficulty.initial_health())
+    }
+
+    fn max_health(&self) -> i32 {
+        self.max_health.unwrap_or(self.difficulty.max_health())
+    }
+
+    fn initial_spawn_interval(&self) -> f64 {
+        self.initial_spawn_interval.unwrap_or(self.difficulty.initial_spawn_interval())
+    }
+
+    fn min_spawn_interval(&self) -> f64 {
+        self.min_spawn_interval.unwrap_or(MIN_SPAWN_INTERVAL)
+    }
+
+    fn initial_card_speed(&self) -> f64 {
+        self.initial_card_speed.unwrap_or(self.difficulty.initial_card_speed())
+    }
+
+    // Thresholds are divisors, so they are kept at 1 or above.
+    fn score_per_max_card_increase(&self) -> i32 {
+        self.score_per_max_card_increase.unwrap_or(self.difficulty.score_per_max_card_increase()).max(1)
+    }
+
+    fn score_per_spawn_interval_decrease(&self) -> i32 {
+        self.score_per_spawn_interval_decrease.unwrap_or(self.difficulty.score_per_spawn_interval_decrease()).max(1)
+    }
+
+    fn score_per_heart(&self) -> i32 {
+        self.score_per_heart.unwrap_or(SCORE_PER_HEART).max(1)
+    }
+
+    fn slowmo_combo_threshold(&self) -> u32 {
+        self.slowmo_combo_threshold.unwrap_or(SLOWMO_COMBO_THRESHOLD)
+    }
+
+    fn autostart(&self) -> bool {
+        self.autostart.unwrap_or(true)
+    }
+
+    fn card_width(&self) -> f64 {
+        self.card_width.unwrap_or(CARD_WIDTH).max(0.0)
+    }
+
+    fn card_height(&self) -> f64 {
+        self.card_height.unwrap_or(CARD_HEIGHT).max(0.0)
+    }
+
+    fn min_card_width(&self) -> f64 {
+        self.min_card_width.unwrap_or(self.card_width()).max(0.0)
+    }
+
+    fn max_card_width(&self) -> f64 {
+        self.max_card_width.unwrap_or(MAX_CARD_WIDTH).max(self.min_card_width())
+    }
+
+    fn flip_display_seconds(&self) -> f64 {
+        self.flip_display_seconds.unwrap_or(FLIP_DISPLAY_SECONDS).max(MIN_FLIP_DISPLAY_SECONDS)
+    }
+
+    fn normalize_options(&self) -> NormalizeOptions<'_> {
+        let keeps_diacritics = !self.strip_diacritics.unwrap_or(true);
+        NormalizeOptions {
+            mode: if keeps_diacritics { TextNormalization::Unicode } else { self.normalization },
+            case_sensitive: self.case_sensitive,
+            ignore_word_order: self.ignore_word_order,
+            stopwords: &self.stopwords,
+        }
+    }
+
+    fn free_misses_for_new_cards(&self) -> u32 {
+        self.free_misses_for_new_cards.unwrap_or(self.difficulty.free_misses_for_new_cards())
+    }
 }
 
 #[derive(Serialize)]
@@ -119,6 +712,198 @@ struct RenderableCard<'a> {
     y: f64,
     flipped: bool,
     free_misses: u32,
+    hint: Option<&'a str>,
+    width: f64,
+    show_answer: bool,
+}
+
+impl<'a> From<&'a Card> for RenderableCard<'a> {
+    fn from(card: &'a Card) -> Self {
+        RenderableCard {
+            id: card.id,
+            front: &card.front,
+            back: &card.back,
+            x: card.x,
+            y: card.y,
+            flipped: card.flipped,
+            free_misses: card.free_misses,
+            hint: card.hint.as_deref(),
+            width: card.width,
+            show_answer: card.preview_until.is_some(),
+        }
+    }
+}
+
+// Things that happened during `tick` or `submit_answer`, collected until the
+// frontend drains them to play sounds and animations.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(tag = "type")]
+enum GameEvent {
+    CardSpawned { id: u32 },
+    CardMissed { id: u32 },
+    CardCleared { id: u32 },
+    HeartGained,
+    HeartLost,
+    CardsUnlocked { count: usize },
+    GameOver,
+}
+
+// Outcome of `Game::simulate`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimulationResult {
+    pub score: i32,
+    pub survival_time: f64,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct SubmitResult {
+    removed_ids: Vec<u32>,
+    score: i32,
+    unlocked: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct ComboState {
+    streak: u32,
+    multiplier: u32,
+    // Seconds until the streak resets without a correct answer, 0 without one
+    seconds_left: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GameState {
+    score: i32,
+    health: i32,
+    max_health: i32,
+    game_over: bool,
+    paused: bool,
+    spawn_interval: f64,
+    card_speed: f64,
+    unlocked_count: usize,
+}
+
+// A missed card with how often it was missed this game.
+#[derive(Serialize)]
+struct MissedCardSummary<'a> {
+    #[serde(flatten)]
+    card: &'a Card,
+    times_missed: u32,
+}
+
+// A missed card with its answer counts over all games.
+#[derive(Serialize)]
+struct MissedCardCounts<'a> {
+    #[serde(flatten)]
+    card: &'a Card,
+    miss_count: u32,
+    success_count: u32,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct UnlockProgress {
+    unlocked: usize,
+    total: usize,
+    // Raw points still needed, None once every card is unlocked
+    score_to_next_unlock: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct UpcomingCard {
+    front: String,
+    back: String,
+}
+
+#[derive(Serialize)]
+struct CardPreview<'a> {
+    raw_front: &'a str,
+    raw_back: &'a str,
+}
+
+// The most recent miss, kept briefly so it can be undone.
+#[derive(Serialize, Deserialize, Clone)]
+struct LastMiss {
+    raw_front: String,
+    age: f64,
+    cost_health: bool,
+    // Scheduling and streaks from just before the miss, restored by an undo
+    #[serde(default)]
+    previous_box: Option<u32>,
+    #[serde(default)]
+    previous_schedule: Option<CardSchedule>,
+    #[serde(default)]
+    previous_combo_streak: u32,
+    #[serde(default)]
+    previous_streak: u32,
+}
+
+// A player input that changed the game, in the order it was applied.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type")]
+enum ReplayAction {
+    Tick { dt: f64 },
+    Submit { answer: String, tolerance: u32 },
+    Skip { id: u32 },
+    GiveUp,
+    Reshuffle,
+    Slowmo { duration: f64 },
+    Reseed { seed: u64 },
+    Choices { id: u32, n: usize },
+    Choice { id: u32, chosen: String },
+    Hint { id: u32 },
+    Mode { mode: GameMode },
+    BurstProbability { p: f64 },
+    BlitzAcceleration { factor: f64 },
+    BottomMargin { margin: f64 },
+    MaxCardsCap { cap: usize },
+    FlipDisplaySeconds { secs: f64 },
+    UndoMiss,
+    SpeedMultiplier { multiplier: f64 },
+    RngWordPos { word_pos: u64 },
+    Exclude { raw_front: String },
+    Include { raw_front: String },
+    LoadStats { success: HashMap<String, u32>, miss: HashMap<String, u32> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct ReplayEntry {
+    time: f64,
+    action: ReplayAction,
+}
+
+// Everything besides seed, mode and deck needed to reproduce a session.
+#[derive(Serialize, Deserialize)]
+struct Replay {
+    width: f64,
+    height: f64,
+    speed_multiplier: f64,
+    config: GameConfig,
+    actions: Vec<ReplayEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CardProgress {
+    raw_front: String,
+    box_level: u32,
+    duplicates: u32,
+    success_count: u32,
+    miss_count: u32,
+    mastered: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DeckProgress {
+    cards: Vec<CardProgress>,
+    mastered: usize,
+    in_progress: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Statistics {
+    total_answers: u32,
+    total_misses: u32,
+    accuracy_percent: f64,
+    current_streak: u32,
+    best_streak: u32,
 }
 
 impl Default for Game {
@@ -130,14 +915,19 @@ impl Default for Game {
             unlocked_cards_count: 0,
             card_miss_counts: HashMap::new(),
             card_success_counts: HashMap::new(),
+            card_boxes: HashMap::new(),
+            card_schedules: HashMap::new(),
+            card_latencies: HashMap::new(),
+            deck_round: 0,
             width: 600.0,
             height: 800.0,
             score: 0,
+            raw_score: 0,
             time_since_last_card: 0.0,
             card_spawn_interval: INITIAL_SPAWN_INTERVAL,
             card_speed: INITIAL_CARD_SPEED,
-            health: 3,
-            max_health: 5,
+            health: INITIAL_HEALTH,
+            max_health: MAX_HEALTH,
             score_since_last_heart: 0,
             game_over: false,
             paused: false,
@@ -148,50 +938,118 @@ impl Default for Game {
             next_card_id: 0,
             speed_multiplier: 1.0,
             card_data: vec![],
+            fuzzy_tolerance: 0,
+            config: GameConfig::default(),
+            skips_remaining: INITIAL_SKIPS,
+            time_remaining: None,
+            combo_streak: 0,
+            time_since_last_correct: 0.0,
+            time_since_last_damage: None,
+            last_miss: None,
+            total_response_time: 0.0,
+            response_count: 0,
+            elapsed: 0.0,
+            replay_log: vec![],
+            replay_config: GameConfig::default(),
+            replay_speed_multiplier: 1.0,
+            queued_answers: Vec::new(),
+            category_scores: HashMap::new(),
+            slowmo_remaining: 0.0,
+            hints_used: HashSet::new(),
+            excluded: HashSet::new(),
+            answer_digest: FNV_OFFSET_BASIS,
+            current_streak: 0,
+            best_streak: 0,
+            events: vec![],
         }
     }
 }
 
 // This will expand text with parentheses into multiple variations.
-// E.g., "card(s)" becomes ["card", "cards"].
+// E.g., "card(s)" becomes ["card", "cards"]. Groups may nest, so "a(b(c))"
+// becomes ["a", "ab", "abc"], and `\(` / `\)` stand for literal parentheses.
 fn expand_parens(text: &str) -> Vec<String> {
-    let re = Regex::new(r"\((.*?)\)").unwrap();
-    let mut results: HashSet<String> = HashSet::new();
-    results.insert(text.to_string());
-
-    loop {
-        let mut next_results = HashSet::new();
-        let mut changed_in_iteration = false;
-
-        for s in results {
-            if let Some(cap) = re.captures(&s) {
-                changed_in_iteration = true;
-                let full_match = cap.get(0).unwrap().as_str();
-                let content = cap.get(1).unwrap().as_str();
-
-                next_results.insert(s.replacen(full_match, content, 1));
-                next_results.insert(s.replacen(full_match, "", 1));
-            } else {
-                next_results.insert(s);
-            }
+    let chars: Vec<char> = text.chars().collect();
+    let mut variations: Vec<String> = Vec::new();
+    for variation in expand_chars(&chars) {
+        let variation = variation.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !variation.is_empty() && !variations.contains(&variation) {
+            variations.push(variation);
         }
+    }
+    variations
+}
 
-        results = next_results;
-        if !changed_in_iteration {
-            break;
+fn is_escaped_paren(chars: &[char], i: usize) -> bool {
+    chars[i] == '\\' && matches!(chars.get(i + 1), Some('(' | ')'))
+}
+
+fn expand_chars(chars: &[char]) -> Vec<String> {
+    let mut results = vec![String::new()];
+    let mut i = 0;
+    while i < chars.len() {
+        if is_escaped_paren(chars, i) {
+            results.iter_mut().for_each(|r| r.push(chars[i + 1]));
+            i += 2;
+            continue;
+        }
+        match (chars[i], matching_paren(chars, i)) {
+            ('(', Some(close)) => {
+                let group = expand_chars(&chars[i + 1..close]);
+                results = results
+                    .iter()
+                    .flat_map(|prefix| {
+                        std::iter::once(prefix.clone()).chain(group.iter().map(move |g| format!("{}{}", prefix, g)))
+                    })
+                    .collect();
+                i = close + 1;
+            }
+            // Unbalanced parentheses are kept as they are
+            (c, _) => {
+                results.iter_mut().for_each(|r| r.push(c));
+                i += 1;
+            }
         }
     }
+    results
+}
 
-    results.into_iter()
-        .map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
-        .filter(|s| !s.is_empty())
-        .collect()
+// Index of the `)` closing the group opened at `open`, if any.
+fn matching_paren(chars: &[char], open: usize) -> Option<usize> {
+    if chars[open] != '(' {
+        return None;
+    }
+    let mut depth = 0;
+    let mut i = open;
+    while i < chars.len() {
+        if is_escaped_paren(chars, i) {
+            i += 2;
+            continue;
+        }
+        match chars[i] {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
 }
 
-fn process_side(text: &str) -> String {
-    let parts: Vec<String> = text.split('/')
+// Separates alternative answers on a card side. Decks may add more separators
+// with a `#separators:` header; sides are stored with alternatives joined by
+// " / ", whichever separator the deck used.
+const ALTERNATIVE_SEPARATOR: char = '/';
+
+fn process_side(text: &str, separators: &[char]) -> String {
+    let parts: Vec<String> = text.split(separators)
         .map(|s| s.trim())
-        .flat_map(|part| expand_parens(part))
+        .flat_map(expand_parens)
         .collect();
     
     // Deduplicate
@@ -205,58 +1063,319 @@ fn process_side(text: &str) -> String {
     unique_parts.join(" / ")
 }
 
-#[wasm_bindgen]
-pub fn parse_deck(text: &str) -> JsValue {
-    let cards: Vec<CustomCard> = text
-        .lines()
-        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
-        .filter_map(|line| {
-            let parts: Vec<&str> = line.split('\t').collect();
-            if parts.len() >= 2 {
-                let front = process_side(parts[0].trim());
-                let back = process_side(parts[1].trim());
-                Some(CustomCard { front, back })
-            } else {
-                None
-            }
-        })
-        .collect();
-    serde_wasm_bindgen::to_value(&cards).unwrap()
+// Masks the first alternative of a card back, keeping its first character and
+// any spaces, e.g. "Good / Fine" becomes "G___".
+fn mask_answer(back: &str) -> String {
+    let first_alternative = back.split(ALTERNATIVE_SEPARATOR).next().unwrap_or("").trim();
+    first_alternative
+        .chars()
+        .enumerate()
+        .map(|(i, c)| if i == 0 || c.is_whitespace() { c } else { '_' })
+        .collect()
 }
 
+// Picks the column delimiter of a deck file. An Anki-style `#separator:` header
+// wins, otherwise the first data line is sniffed for a tab and then a comma.
+fn detect_delimiter(text: &str) -> char {
+    for line in text.lines() {
+        if let Some(separator) = line.strip_prefix("#separator:") {
+            match separator.trim().to_lowercase().as_str() {
+                "tab" => return '\t',
+                "comma" => return ',',
+                "semicolon" => return ';',
+                "pipe" => return '|',
+                "colon" => return ':',
+                "space" => return ' ',
+                _ => {}
+            }
+        }
+    }
 
-#[wasm_bindgen]
-pub fn configure_deck(full_deck: JsValue, ordered_indices: JsValue) -> Result<JsValue, JsValue> {
-    let deck: Vec<CustomCard> = serde_wasm_bindgen::from_value(full_deck)?;
-    let indices: Vec<usize> = serde_wasm_bindgen::from_value(ordered_indices)?;
-
-    let configured_deck: Vec<CustomCard> = indices
-        .into_iter()
-        .filter_map(|i| deck.get(i).cloned())
-        .collect();
-    
-    serde_wasm_bindgen::to_value(&configured_deck).map_err(|e| e.into())
+    let first_data_line = text
+        .lines()
+        .find(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .unwrap_or("");
+    if !first_data_line.contains('\t') && first_data_line.contains(',') {
+        ','
+    } else {
+        '\t'
+    }
 }
 
-impl Game {
-    fn get_available_cards_data(&self) -> &[(String, String)] {
-        let num_available_cards = INITIAL_UNLOCKED_CARDS
-            + (self.score / SCORE_PER_CARD_UNLOCK) as usize * CARDS_PER_UNLOCK;
-        &self.card_data[..num_available_cards.min(self.card_data.len())]
+// Column index of the tags, from an Anki-style `#tags column:N` header (1-based).
+// `/` plus the characters listed in a `#separators:` header, e.g. `#separators:;|`.
+fn detect_alternative_separators(text: &str) -> Vec<char> {
+    let extra: Vec<char> = text
+        .lines()
+        .find_map(|line| line.strip_prefix("#separators:"))
+        .map(|header| header.chars().filter(|c| !c.is_whitespace()).collect())
+        .unwrap_or_default();
+    let mut separators = vec![ALTERNATIVE_SEPARATOR];
+    for c in extra {
+        if !separators.contains(&c) {
+            separators.push(c);
+        }
     }
+    separators
 }
 
-#[wasm_bindgen]
-impl Game {
-    pub fn new(width: f64, height: f64, seed: u64, mode: GameMode, speed_multiplier: f64, custom_deck: JsValue) -> Result<Game, JsValue> {
-        let custom_cards: Vec<CustomCard> = serde_wasm_bindgen::from_value(custom_deck)?;
-        let card_data: Vec<(String, String)> = custom_cards
-            .into_iter()
-            .map(|c| (c.front, c.back))
-            .collect();
+fn detect_tags_column(text: &str) -> Option<usize> {
+    detect_header_column(text, "#tags column:")
+}
+
+// Column index from a `<header>N` line (1-based), skipping front and back.
+fn detect_header_column(text: &str, header: &str) -> Option<usize> {
+    text.lines()
+        .filter_map(|line| line.strip_prefix(header))
+        .find_map(|column| column.trim().parse::<usize>().ok())
+        .filter(|&column| column > 2)
+        .map(|column| column - 1)
+}
+
+// Splits a deck line into its columns. Tab-separated lines are split verbatim
+// as before; for other delimiters, double-quoted fields may contain the
+// delimiter and use `""` for a literal quote, as in spreadsheet CSV exports.
+fn split_fields(line: &str, delimiter: char) -> Vec<String> {
+    if delimiter == '\t' {
+        return line.split('\t').map(|s| s.to_string()).collect();
+    }
+
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == '"' && field.trim().is_empty() {
+            field.clear();
+            in_quotes = true;
+        } else if c == delimiter {
+            fields.push(std::mem::take(&mut field));
+        } else {
+            field.push(c);
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+// Escapes a card side for the `#html:true` tab-separated export so that each
+// card stays on one line with a single separator. Ampersands that already
+// start an HTML entity are left alone.
+fn escape_anki_field(text: &str) -> String {
+    static ENTITY: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z][a-zA-Z0-9]*);").unwrap());
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '&' if ENTITY.is_match(&text[i..]) => escaped.push('&'),
+            '&' => escaped.push_str("&amp;"),
+            '\t' => escaped.push_str("&#9;"),
+            '\r' if chars.peek().is_some_and(|&(_, next)| next == '\n') => {}
+            '\r' | '\n' => escaped.push_str("<br>"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn parse_cards(text: &str) -> Vec<CustomCard> {
+    let delimiter = detect_delimiter(text);
+    let separators = detect_alternative_separators(text);
+    // Without a `#tags column:` header, the third column is a hint and the fourth holds tags
+    let tags_column = detect_tags_column(text).unwrap_or(3);
+    // The category follows the tags unless a `#category column:` header says otherwise
+    let category_column = detect_header_column(text, "#category column:").unwrap_or(tags_column + 1);
+    // Likewise, the points for clearing a card follow the category
+    let points_column = detect_header_column(text, "#points column:").unwrap_or(category_column + 1);
+    // A header claiming the third column takes it away from the hint
+    let hint_column = Some(2).filter(|column| ![tags_column, category_column, points_column].contains(column));
+    text
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let parts = split_fields(line, delimiter);
+            if parts.len() >= 2 {
+                let front = process_side(parts[0].trim(), &separators);
+                let back = process_side(parts[1].trim(), &separators);
+                let hint = hint_column
+                    .and_then(|column| parts.get(column))
+                    .map(|h| h.trim())
+                    .filter(|h| !h.is_empty())
+                    .map(|h| h.to_string());
+                // Tags are separated by spaces (as in Anki) or commas
+                let tags = parts.get(tags_column)
+                    .map(|t| t.split([',', ' ']).map(|tag| tag.trim()).filter(|tag| !tag.is_empty()).map(|tag| tag.to_string()).collect())
+                    .unwrap_or_default();
+                let category = parts.get(category_column)
+                    .map(|c| c.trim())
+                    .filter(|c| !c.is_empty())
+                    .map(|c| c.to_string());
+                let points = parts.get(points_column).and_then(|p| p.trim().parse::<u32>().ok());
+                Some(CustomCard { front, back, hint, tags, category, points })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[wasm_bindgen]
+pub fn parse_deck(text: &str) -> JsValue {
+    let cards = parse_cards(text);
+    serde_wasm_bindgen::to_value(&cards).unwrap()
+}
+
+// Reads a JSON array of `{front, back}` objects (optionally with `hint`,
+// `tags`, `category` and `points`) and processes both sides like `parse_cards` does.
+fn parse_json_cards(text: &str) -> Result<Vec<CustomCard>, String> {
+    let cards: Vec<CustomCard> = serde_json::from_str(text).map_err(|e| format!("Invalid JSON deck: {}", e))?;
+    Ok(cards
+        .into_iter()
+        .map(|card| CustomCard {
+            front: process_side(card.front.trim(), &[ALTERNATIVE_SEPARATOR]),
+            back: process_side(card.back.trim(), &[ALTERNATIVE_SEPARATOR]),
+            ..card
+        })
+        .collect())
+}
+
+/// Like `parse_deck`, but for a JSON array of `{front, back}` objects.
+#[wasm_bindgen]
+pub fn parse_deck_json(text: &str) -> Result<JsValue, JsValue> {
+    let cards = parse_json_cards(text).map_err(|e| JsValue::from_str(&e))?;
+    Ok(serde_wasm_bindgen::to_value(&cards).unwrap())
+}
+
+// A card front that occurs on more than one line of a deck file.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct DuplicateFront {
+    front: String,
+    // 1-based line numbers in the deck file
+    lines: Vec<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct DeckReport {
+    duplicates: Vec<DuplicateFront>,
+}
+
+// Finds fronts that would share statistics, as those are keyed by front.
+fn find_duplicate_fronts(text: &str) -> Vec<DuplicateFront> {
+    let delimiter = detect_delimiter(text);
+    let separators = detect_alternative_separators(text);
+    let mut lines_by_front: Vec<(String, Vec<usize>)> = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let parts = split_fields(line, delimiter);
+        if parts.len() < 2 {
+            continue;
+        }
+        let front = process_side(parts[0].trim(), &separators);
+        match lines_by_front.iter_mut().find(|(f, _)| *f == front) {
+            Some((_, lines)) => lines.push(index + 1),
+            None => lines_by_front.push((front, vec![index + 1])),
+        }
+    }
+    lines_by_front
+        .into_iter()
+        .filter(|(_, lines)| lines.len() > 1)
+        .map(|(front, lines)| DuplicateFront { front, lines })
+        .collect()
+}
+
+/// Checks a deck file for problems without changing how it is parsed.
+/// Currently reports fronts that occur more than once.
+#[wasm_bindgen]
+pub fn validate_deck(text: &str) -> JsValue {
+    let report = DeckReport { duplicates: find_duplicate_fronts(text) };
+    serde_wasm_bindgen::to_value(&report).unwrap()
+}
+
+// Rejects decks that cannot be played, naming the first offending card.
+fn check_card_data(card_data: &[CustomCard]) -> Result<(), String> {
+    if card_data.is_empty() {
+        return Err("Custom deck cannot be empty.".to_string());
+    }
+    for (index, card) in card_data.iter().enumerate() {
+        if card.front.trim().is_empty() {
+            return Err(format!("Card {} has an empty front.", index));
+        }
+        if card.back.trim().is_empty() {
+            return Err(format!("Card {} has an empty back.", index));
+        }
+    }
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub fn configure_deck(full_deck: JsValue, ordered_indices: JsValue) -> Result<JsValue, JsValue> {
+    let deck: Vec<CustomCard> = serde_wasm_bindgen::from_value(full_deck)?;
+    let indices: Vec<usize> = serde_wasm_bindgen::from_value(ordered_indices)?;
+
+    let configured_deck: Vec<CustomCard> = indices
+        .into_iter()
+        .filter_map(|i| deck.get(i).cloned())
+        .collect();
+    
+    serde_wasm_bindgen::to_value(&configured_deck).map_err(|e| e.into())
+}
+
+impl Game {
+    /// Native counterpart of `new` for tools and tests outside the browser,
+    /// taking the deck as text in the `parse_deck` format and default settings.
+    pub fn from_deck_text(width: f64, height: f64, seed: u64, mode: GameMode, speed_multiplier: f64, deck: &str) -> Result<Game, String> {
+        Self::with_deck(width, height, seed, mode, speed_multiplier, parse_cards(deck), GameConfig::default())
+    }
+
+    /// Plays up to `steps` ticks of `dt` seconds on autopilot. After each tick
+    /// the lowest falling card is answered correctly with probability
+    /// `answer_accuracy`, decided by a separate rng seeded like the game, so
+    /// the cards spawn as in a real game. Stops early when the game ends.
+    pub fn simulate(&mut self, answer_accuracy: f64, steps: usize, dt: f64) -> SimulationResult {
+        let mut autopilot = ChaCha8Rng::seed_from_u64(self.rng_seed);
+        for _ in 0..steps {
+            if self.game_over {
+                break;
+            }
+            self.tick(dt);
+            let lowest_answer = self
+                .cards
+                .iter()
+                .filter(|card| !card.flipped)
+                .max_by(|a, b| a.y.total_cmp(&b.y))
+                .map(|card| card.back.split(ALTERNATIVE_SEPARATOR).next().unwrap_or("").trim().to_string());
+            if let Some(answer) = lowest_answer {
+                if autopilot.random::<f64>() < answer_accuracy {
+                    self.resolve_answer(&answer, 0);
+                }
+            }
+        }
+        SimulationResult { score: self.score, survival_time: self.elapsed }
+    }
 
-        if card_data.is_empty() {
-            return Err(JsValue::from_str("Custom deck cannot be empty."));
+    // Shared by `new` and `replay` once the deck and config are deserialized.
+    fn with_deck(width: f64, height: f64, seed: u64, mode: GameMode, speed_multiplier: f64, mut card_data: Vec<CustomCard>, config: GameConfig) -> Result<Game, String> {
+        check_card_data(&card_data)?;
+        config.check()?;
+        if !config.tag_filter.is_empty() {
+            card_data.retain(|card| card.tags.iter().any(|tag| config.tag_filter.contains(tag)));
+            if card_data.is_empty() {
+                return Err("No cards match the tag filter.".to_string());
+            }
         }
 
         let mut rng = ChaCha8Rng::seed_from_u64(seed);
@@ -270,11 +1389,385 @@ impl Game {
             game_id,
             mode,
             speed_multiplier,
+            health: config.initial_health(),
+            max_health: config.max_health(),
+            card_spawn_interval: config.initial_spawn_interval(),
+            card_speed: config.initial_card_speed() * speed_multiplier,
+            time_remaining: config.time_limit,
+            replay_config: config.clone(),
+            replay_speed_multiplier: speed_multiplier,
+            config,
             ..Self::default()
         };
         game.card_data = card_data;
-        game.card_speed *= speed_multiplier;
-        game.spawn_card();
+        if game.config.autostart() {
+            game.spawn_card();
+        }
+        Ok(game)
+    }
+
+    // Native part of `replay`.
+    fn replay_with_deck(seed: u64, mode: GameMode, card_data: Vec<CustomCard>, replay_json: &str) -> Result<Game, String> {
+        let replay: Replay = serde_json::from_str(replay_json).map_err(|e| format!("Invalid replay: {}", e))?;
+        let mut game = Self::with_deck(replay.width, replay.height, seed, mode, replay.speed_multiplier, card_data, replay.config)?;
+        for entry in replay.actions {
+            match entry.action {
+                ReplayAction::Tick { dt } => game.tick(dt),
+                ReplayAction::Submit { answer, tolerance } => {
+                    game.resolve_answer(&answer, tolerance);
+                }
+                ReplayAction::Skip { id } => {
+                    game.skip_card(id);
+                }
+                ReplayAction::GiveUp => game.give_up(),
+                ReplayAction::Reshuffle => game.reshuffle_deck(),
+                ReplayAction::Slowmo { duration } => game.activate_slowmo(duration),
+                ReplayAction::Reseed { seed } => game.reseed(seed),
+                ReplayAction::Choices { id, n } => {
+                    game.choices_for_card(id, n);
+                }
+                ReplayAction::Choice { id, chosen } => {
+                    game.submit_choice(id, &chosen);
+                }
+                ReplayAction::Hint { id } => {
+                    game.request_hint(id);
+                }
+                ReplayAction::Mode { mode } => game.set_mode(mode),
+                ReplayAction::BurstProbability { p } => game.set_burst_probability(p),
+                ReplayAction::BlitzAcceleration { factor } => game.set_blitz_acceleration(factor),
+                ReplayAction::BottomMargin { margin } => game.set_bottom_margin(margin),
+                ReplayAction::MaxCardsCap { cap } => game.set_max_cards_cap(cap),
+                ReplayAction::FlipDisplaySeconds { secs } => game.set_flip_display_seconds(secs),
+                ReplayAction::SpeedMultiplier { multiplier } => game.set_speed_multiplier(multiplier),
+                ReplayAction::RngWordPos { word_pos } => game.set_rng_word_pos(word_pos),
+                ReplayAction::Exclude { raw_front } => game.exclude_card(&raw_front),
+                ReplayAction::Include { raw_front } => game.include_card(&raw_front),
+                ReplayAction::LoadStats { success, miss } => game.apply_stats(success, miss),
+                ReplayAction::UndoMiss => {
+                    game.undo_last_miss();
+                }
+            }
+        }
+        Ok(game)
+    }
+
+    fn cards_for_render(&self, include_flipped: bool) -> Vec<RenderableCard<'_>> {
+        let mut render_cards: Vec<RenderableCard> = self.cards
+            .iter()
+            .filter(|card| include_flipped || !card.flipped)
+            .map(RenderableCard::from)
+            .collect();
+        // Stable, so cards at the same height keep their spawn order
+        render_cards.sort_by(|a, b| a.y.total_cmp(&b.y));
+        render_cards
+    }
+
+    fn record(&mut self, action: ReplayAction) {
+        self.replay_log.push(ReplayEntry { time: self.elapsed, action });
+    }
+
+    // Cards in higher Leitner boxes are known better and get fewer copies in
+    // the deck: box 0 gets the full count, the top box a single copy, and the
+    // boxes in between are spread linearly.
+    fn deck_duplicates_for(&self, raw_front: &str) -> u32 {
+        let box_level = self.card_boxes.get(raw_front).cloned().unwrap_or(0).min(MAX_LEITNER_BOX);
+        let extra_copies = self.config.deck.deck_card_duplicates() - 1;
+        let base = 1 + extra_copies * (MAX_LEITNER_BOX - box_level) / MAX_LEITNER_BOX;
+        base + self.miss_extra_duplicates(raw_front)
+    }
+
+    // Cards missed more often than answered get one extra copy per miss
+    // beyond their successes, scaled by the deck's miss weight. Unseen cards
+    // get none.
+    fn miss_extra_duplicates(&self, raw_front: &str) -> u32 {
+        let misses = self.card_miss_counts.get(raw_front).cloned().unwrap_or(0);
+        let successes = self.card_success_counts.get(raw_front).cloned().unwrap_or(0);
+        let extra = self.config.deck.miss_weight() * misses.saturating_sub(successes) as f64;
+        (extra.round() as u32).min(MAX_MISS_EXTRA_DUPLICATES)
+    }
+
+    // Takes the next deck entry whose front is not already falling, looking a
+    // few entries deep. If all of those are on screen, the next one is used anyway.
+    fn draw_from_deck(&mut self) -> Option<CustomCard> {
+        let index = self.next_deck_index()?;
+        Some(self.card_deck.remove(index))
+    }
+
+    // Index of the deck entry `draw_from_deck` takes next, if any.
+    fn next_deck_index(&self) -> Option<usize> {
+        let falling: HashSet<&str> = self.cards.iter().filter(|card| !card.flipped).map(|card| card.raw_front.as_str()).collect();
+        self.card_deck
+            .iter()
+            .enumerate()
+            .rev()
+            .take(MAX_SPAWN_REDRAWS)
+            .find(|(_, card)| !falling.contains(card.front.as_str()))
+            .map(|(index, _)| index)
+            .or(self.card_deck.len().checked_sub(1))
+    }
+
+    // The entry `draw_from_deck` takes next, followed by the rest of the deck
+    // in drawing order.
+    fn upcoming_cards(&self, n: usize) -> Vec<UpcomingCard> {
+        let next_index = self.next_deck_index();
+        let rest = self.card_deck.iter().enumerate().rev().filter(|&(index, _)| Some(index) != next_index);
+        next_index
+            .map(|index| (index, &self.card_deck[index]))
+            .into_iter()
+            .chain(rest)
+            .take(n)
+            .map(|(_, card)| {
+                let (front, back) = if self.mode == GameMode::Reverse {
+                    (&card.back, &card.front)
+                } else {
+                    (&card.front, &card.back)
+                };
+                UpcomingCard { front: front.clone(), back: back.clone() }
+            })
+            .collect()
+    }
+
+    // Picks the x farthest from the falling cards, so simultaneous cards don't
+    // overlap. Candidates are spread evenly across the board from a random
+    // offset. With nothing falling, that offset is used directly.
+    fn pick_spawn_x(&mut self, card_width: f64) -> f64 {
+        let range = self.width - card_width;
+        if range <= 0.0 {
+            return 0.0;
+        }
+        if let Some(columns) = self.config.spawn_columns.filter(|&columns| columns > 0) {
+            let column = self.rng.random_range(0..columns);
+            return Self::column_center(column, columns, range);
+        }
+        let active_xs: Vec<f64> = self.cards.iter().filter(|card| !card.flipped).map(|card| card.x).collect();
+        if active_xs.is_empty() {
+            return self.rng.random_range(0.0..range);
+        }
+
+        let step = range / SPAWN_POSITION_CANDIDATES as f64;
+        let offset = self.rng.random_range(0.0..step);
+        let distance_to_nearest = |x: f64| active_xs.iter().map(|other| (x - other).abs()).fold(f64::INFINITY, f64::min);
+        (0..SPAWN_POSITION_CANDIDATES)
+            .map(|i| offset + i as f64 * step)
+            .max_by(|a, b| distance_to_nearest(*a).total_cmp(&distance_to_nearest(*b)))
+            .unwrap()
+    }
+
+    // X of the middle of a column when `range` is split into `columns` equal parts.
+    fn column_center(column: usize, columns: usize, range: f64) -> f64 {
+        (column as f64 + 0.5) * range / columns as f64
+    }
+
+    // Replaces the answer counts with saved ones. Leitner boxes follow the net
+    // successes, and the deck and falling cards are updated to match.
+    fn apply_stats(&mut self, success_counts: HashMap<String, u32>, miss_counts: HashMap<String, u32>) {
+        self.record(ReplayAction::LoadStats { success: success_counts.clone(), miss: miss_counts.clone() });
+        self.card_boxes = success_counts
+            .iter()
+            .map(|(raw_front, &successes)| {
+                let misses = miss_counts.get(raw_front).cloned().unwrap_or(0);
+                (raw_front.clone(), successes.saturating_sub(misses).min(MAX_LEITNER_BOX))
+            })
+            .collect();
+        self.card_success_counts = success_counts;
+        self.card_miss_counts = miss_counts;
+        let free_misses_for_new_cards = self.config.free_misses_for_new_cards();
+        for card in self.cards.iter_mut().filter(|card| !card.flipped) {
+            let successes = self.card_success_counts.get(&card.raw_front).cloned().unwrap_or(0);
+            let misses = self.card_miss_counts.get(&card.raw_front).cloned().unwrap_or(0);
+            card.free_misses = free_misses_for_new_cards.saturating_sub(successes + misses);
+        }
+        self.replenish_deck();
+    }
+
+    // Wide enough for the front text, within the configured bounds and the board.
+    fn card_width_for(&self, front: &str) -> f64 {
+        let text_width = front.chars().count() as f64 * CARD_CHAR_WIDTH + CARD_TEXT_PADDING;
+        text_width
+            .clamp(self.config.min_card_width(), self.config.max_card_width())
+            .min(self.width)
+    }
+
+    // Missed cards in the order they were missed, keeping the first of each front.
+    fn unique_missed_cards(&self) -> Vec<&Card> {
+        let mut seen_fronts = HashSet::new();
+        self.missed_cards
+            .iter()
+            .filter(|card| seen_fronts.insert(&card.raw_front))
+            .collect()
+    }
+
+    // One entry per missed front, most missed first. Ties keep the order in
+    // which the cards were first missed.
+    fn missed_card_summaries(&self) -> Vec<MissedCardSummary<'_>> {
+        let mut summaries: Vec<MissedCardSummary> = self
+            .unique_missed_cards()
+            .into_iter()
+            .map(|card| MissedCardSummary {
+                card,
+                times_missed: self.missed_cards.iter().filter(|missed| missed.raw_front == card.raw_front).count() as u32,
+            })
+            .collect();
+        summaries.sort_by_key(|summary| std::cmp::Reverse(summary.times_missed));
+        summaries
+    }
+
+    // One entry per front missed this game with its total counts, most
+    // missed first.
+    fn missed_card_counts(&self) -> Vec<MissedCardCounts<'_>> {
+        let mut counts: Vec<MissedCardCounts> = self
+            .unique_missed_cards()
+            .into_iter()
+            .map(|card| MissedCardCounts {
+                card,
+                miss_count: self.card_miss_counts.get(&card.raw_front).cloned().unwrap_or(0),
+                success_count: self.card_success_counts.get(&card.raw_front).cloned().unwrap_or(0),
+            })
+            .collect();
+        counts.sort_by_key(|entry| std::cmp::Reverse(entry.miss_count));
+        counts
+    }
+
+    fn combo_state(&self) -> ComboState {
+        let seconds_left = if self.combo_streak > 0 { (COMBO_WINDOW_SECONDS - self.time_since_last_correct).max(0.0) } else { 0.0 };
+        ComboState { streak: self.combo_streak, multiplier: self.get_combo_multiplier(), seconds_left }
+    }
+
+    // Card speed for the current score before the speed multiplier.
+    fn score_card_speed(&self) -> f64 {
+        if self.config.constant_difficulty {
+            return self.config.initial_card_speed();
+        }
+        self.config.initial_card_speed() + self.score as f64 * CARD_SPEED_INCREASE_PER_SCORE
+    }
+
+    // Never-seen cards are always due.
+    fn is_due(&self, raw_front: &str) -> bool {
+        self.card_schedules
+            .get(raw_front)
+            .is_none_or(|schedule| schedule.due_round <= self.deck_round)
+    }
+
+    // Cards unlocked by score, including excluded ones.
+    fn unlocked_cards_data(&self) -> &[CustomCard] {
+        let deck_config = &self.config.deck;
+        if deck_config.unlock_all {
+            return &self.card_data;
+        }
+        let num_available_cards = deck_config.initial_unlocked_cards()
+            + (self.raw_score / deck_config.score_per_card_unlock()) as usize * deck_config.cards_per_unlock();
+        &self.card_data[..num_available_cards.min(self.card_data.len())]
+    }
+
+    fn unlock_progress(&self) -> UnlockProgress {
+        let unlocked = self.unlocked_cards_data().len();
+        let total = self.card_data.len();
+        let score_per_card_unlock = self.config.deck.score_per_card_unlock();
+        let score_to_next_unlock = (unlocked < total)
+            .then(|| (self.raw_score.max(0) / score_per_card_unlock + 1) * score_per_card_unlock - self.raw_score);
+        UnlockProgress { unlocked, total, score_to_next_unlock }
+    }
+
+    // Unlocked cards that may be put into the deck.
+    fn get_available_cards_data(&self) -> Vec<&CustomCard> {
+        self.unlocked_cards_data()
+            .iter()
+            .filter(|card| !self.excluded.contains(&card.front))
+            .collect()
+    }
+}
+
+#[wasm_bindgen]
+impl Game {
+    pub fn new(width: f64, height: f64, seed: u64, mode: GameMode, speed_multiplier: f64, custom_deck: JsValue, config: JsValue) -> Result<Game, JsValue> {
+        let card_data: Vec<CustomCard> = serde_wasm_bindgen::from_value(custom_deck)?;
+        let config: GameConfig = serde_wasm_bindgen::from_value::<Option<GameConfig>>(config)?.unwrap_or_default();
+        Self::with_deck(width, height, seed, mode, speed_multiplier, card_data, config).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Like `new` with default settings apart from the difficulty preset,
+    /// which is kept across restarts.
+    pub fn new_with_preset(width: f64, height: f64, seed: u64, mode: GameMode, speed_multiplier: f64, custom_deck: JsValue, preset: Difficulty) -> Result<Game, JsValue> {
+        let card_data: Vec<CustomCard> = serde_wasm_bindgen::from_value(custom_deck)?;
+        let config = GameConfig { difficulty: preset, ..GameConfig::default() };
+        Self::with_deck(width, height, seed, mode, speed_multiplier, card_data, config).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Serializes the complete game, including the position in the random
+    /// stream, to a JSON string that `from_save_state` can restore.
+    pub fn to_save_state(&self) -> String {
+        let state = SaveState {
+            version: SAVE_STATE_VERSION,
+            rng_word_pos: self.rng.get_word_pos(),
+            game: self,
+        };
+        serde_json::to_string(&state).unwrap()
+    }
+
+    /// Alias of `to_save_state`.
+    pub fn serialize_state(&self) -> String {
+        self.to_save_state()
+    }
+
+    /// Alias of `from_save_state`.
+    pub fn from_state(json: &str) -> Result<Game, JsValue> {
+        Self::from_save_state(json)
+    }
+
+    /// The inputs of this game so far as JSON, for `Game::replay`.
+    pub fn export_replay(&self) -> String {
+        let replay = Replay {
+            width: self.width,
+            height: self.height,
+            speed_multiplier: self.replay_speed_multiplier,
+            config: self.replay_config.clone(),
+            actions: self.replay_log.clone(),
+        };
+        serde_json::to_string(&replay).unwrap()
+    }
+
+    /// Starts a game with the same seed, mode and deck as a recorded one and
+    /// applies its inputs, returning the resulting game.
+    pub fn replay(seed: u64, mode: GameMode, custom_deck: JsValue, replay_json: &str) -> Result<Game, JsValue> {
+        let card_data: Vec<CustomCard> = serde_wasm_bindgen::from_value(custom_deck)?;
+        Self::replay_with_deck(seed, mode, card_data, replay_json).map_err(|e| JsValue::from_str(&e))
+    }
+
+    pub fn get_rng_seed(&self) -> u64 {
+        self.rng_seed
+    }
+
+    /// Restarts the random stream from `seed` without touching the score,
+    /// cards or deck. Only later spawns and shuffles are affected.
+    pub fn reseed(&mut self, seed: u64) {
+        self.record(ReplayAction::Reseed { seed });
+        self.rng_seed = seed;
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+    }
+
+    /// Position in the random stream, in 32-bit words drawn since seeding.
+    /// Together with the seed this pins down every future spawn and flip.
+    /// Saturates at `u64::MAX`, far beyond what any game draws.
+    pub fn rng_word_pos(&self) -> u64 {
+        u64::try_from(self.rng.get_word_pos()).unwrap_or(u64::MAX)
+    }
+
+    /// Moves the random stream to a position from `rng_word_pos`, so a game
+    /// with the same seed continues with exactly the same random draws.
+    pub fn set_rng_word_pos(&mut self, word_pos: u64) {
+        self.record(ReplayAction::RngWordPos { word_pos });
+        self.rng.set_word_pos(u128::from(word_pos));
+    }
+
+    pub fn from_save_state(json: &str) -> Result<Game, JsValue> {
+        let state: SaveState<Game> = serde_json::from_str(json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid save state: {}", e)))?;
+        if state.version != SAVE_STATE_VERSION {
+            return Err(JsValue::from_str(&format!("Unsupported save state version {}.", state.version)));
+        }
+        let mut game = state.game;
+        game.rng = ChaCha8Rng::seed_from_u64(game.rng_seed);
+        game.rng.set_word_pos(state.rng_word_pos);
         Ok(game)
     }
 
@@ -282,33 +1775,88 @@ impl Game {
         if self.game_over || self.paused {
             return;
         }
+        // Resolved before recording the tick, so replays submit them in the same order
+        for answer in std::mem::take(&mut self.queued_answers) {
+            self.resolve_answer(&answer, self.fuzzy_tolerance);
+        }
+        if self.game_over {
+            return;
+        }
+        self.record(ReplayAction::Tick { dt });
+        self.elapsed += dt;
+        if let Some(time_remaining) = &mut self.time_remaining {
+            *time_remaining = (*time_remaining - dt).max(0.0);
+            if *time_remaining <= 0.0 {
+                self.end_game();
+                return;
+            }
+        }
+        self.time_since_last_correct += dt;
+        if let Some(time) = &mut self.time_since_last_damage {
+            *time += dt;
+        }
+        if let Some(last_miss) = &mut self.last_miss {
+            last_miss.age += dt;
+        }
+        if self.time_since_last_correct > COMBO_WINDOW_SECONDS {
+            self.combo_streak = 0;
+        }
         self.spawn_new_cards(dt);
         self.update_cards(dt);
+        for card in self.cards.iter_mut() {
+            card.preview_until = card.preview_until.map(|remaining| remaining - dt).filter(|&remaining| remaining > 0.0);
+        }
+        self.slowmo_remaining = (self.slowmo_remaining - dt).max(0.0);
     }
 
     fn spawn_new_cards(&mut self, dt: f64) {
         self.time_since_last_card += dt;
-        let max_cards = INITIAL_MAX_CARDS + (self.score / SCORE_PER_MAX_CARD_INCREASE) as usize;
+        let mut max_cards = INITIAL_MAX_CARDS + (self.score / self.config.score_per_max_card_increase()) as usize;
+        if let Some(cap) = self.config.max_cards_cap {
+            max_cards = max_cards.min(cap);
+        }
 
-        if self.time_since_last_card > self.card_spawn_interval && self.cards.len() < max_cards {
-            self.spawn_card();
+        // Without autostart, the first card waits for the first tick
+        let first_spawn = self.next_card_id == 0;
+        if (first_spawn || self.time_since_last_card > self.card_spawn_interval) && self.cards.len() < max_cards {
+            // The rng is only drawn from when bursts are enabled, so games without
+            // them play out exactly as before.
+            let burst_probability = self.config.burst_probability;
+            let spawn_count = if burst_probability > 0.0 && self.rng.random::<f64>() < burst_probability {
+                self.rng.random_range(MIN_BURST_SIZE..=MAX_BURST_SIZE)
+            } else {
+                1
+            };
+            for _ in 0..spawn_count.min(max_cards - self.cards.len()) {
+                self.spawn_card();
+            }
             self.time_since_last_card = 0.0;
         }
     }
 
     fn update_cards(&mut self, dt: f64) {
         let mut health_damage = 0;
+        let mut missed_any = false;
+        let health_before = self.health;
+        let mut newest_miss = None;
+        let flip_y = self.height - self.config.card_height() - self.config.bottom_margin;
+        let slowmo_factor = if self.is_slowmo_active() { SLOWMO_SPEED_FACTOR } else { 1.0 };
         for card in self.cards.iter_mut() {
             if card.flipped {
                 if let Some(time) = &mut card.time_since_flipped {
                     *time += dt;
                 }
             } else {
-                card.y += self.card_speed * dt;
-                if card.y >= self.height - CARD_HEIGHT {
-                    card.y = self.height - CARD_HEIGHT; // Stop at the bottom
+                card.age += dt;
+                let acceleration = 1.0 + self.config.blitz_acceleration * (card.y / self.height).clamp(0.0, 1.0);
+                card.y += self.card_speed * slowmo_factor * acceleration * dt;
+                if card.y >= flip_y {
+                    card.y = flip_y; // Stop at the bottom
                     card.flipped = true;
                     card.time_since_flipped = Some(0.0);
+                    self.hints_used.remove(&card.id);
+                    missed_any = true;
+                    self.events.push(GameEvent::CardMissed { id: card.id });
                     
                     if card.free_misses == 0 {
                         health_damage += 1;
@@ -316,21 +1864,65 @@ impl Game {
                     
                     let miss_count = self.card_miss_counts.entry(card.raw_front.clone()).or_insert(0);
                     *miss_count += 1;
+                    let previous_box = self.card_boxes.insert(card.raw_front.clone(), 0);
+                    let previous_schedule = self.card_schedules.get(&card.raw_front).cloned();
+                    let (previous_combo_streak, previous_streak) = (self.combo_streak, self.current_streak);
+                    self.card_schedules.entry(card.raw_front.clone()).or_default().record_miss(self.deck_round);
+                    self.combo_streak = 0;
+                    self.current_streak = 0;
 
                     self.missed_cards.push(card.clone());
+                    newest_miss = Some(LastMiss {
+                        raw_front: card.raw_front.clone(),
+                        age: 0.0,
+                        cost_health: card.free_misses == 0,
+                        previous_box,
+                        previous_schedule,
+                        previous_combo_streak,
+                        previous_streak,
+                    });
                 }
             }
         }
 
-        if health_damage > 0 && !self.game_over {
+        let cooldown = self.config.damage_cooldown_seconds;
+        if cooldown > 0.0 && health_damage > 0 {
+            let in_grace_window = self.time_since_last_damage.is_some_and(|time| time < cooldown);
+            health_damage = if in_grace_window { 0 } else { 1 };
+        }
+
+        if health_damage > 0 && !self.game_over && !self.config.practice {
+            self.time_since_last_damage = Some(0.0);
+            let previous_health = self.health;
             self.health = self.health.saturating_sub(health_damage);
+            for _ in self.health..previous_health {
+                self.events.push(GameEvent::HeartLost);
+            }
             if self.health == 0 {
-                self.game_over = true;
+                self.end_game();
             }
         }
 
-        // Remove cards that have been flipped for over 1 second
-        self.cards.retain(|card| card.time_since_flipped.map_or(true, |time| time < 1.0));
+        if let Some(mut miss) = newest_miss {
+            miss.cost_health &= self.health < health_before;
+            self.last_miss = Some(miss);
+        }
+
+        if missed_any && self.config.hardcore && !self.config.practice {
+            self.end_game();
+        }
+
+        // Remove cards once their answer has been shown long enough
+        let flip_display_seconds = self.config.flip_display_seconds();
+        self.cards.retain(|card| card.time_since_flipped.is_none_or(|time| time < flip_display_seconds));
+    }
+
+    fn end_game(&mut self) {
+        self.queued_answers.clear();
+        if !self.game_over {
+            self.game_over = true;
+            self.events.push(GameEvent::GameOver);
+        }
     }
 
     fn spawn_card(&mut self) {
@@ -338,7 +1930,7 @@ impl Game {
             self.replenish_deck();
         }
 
-        if let Some((raw_front, raw_back)) = self.card_deck.pop() {
+        if let Some(CustomCard { front: raw_front, back: raw_back, hint, category, points, .. }) = self.draw_from_deck() {
             let should_reverse =
                 self.mode == GameMode::Reverse || (self.mode == GameMode::Both && self.rng.random());
     
@@ -350,59 +1942,144 @@ impl Game {
     
             let miss_count = self.card_miss_counts.get(&raw_front).cloned().unwrap_or(0);
             let success_count = self.card_success_counts.get(&raw_front).cloned().unwrap_or(0);
-            let total_interactions = miss_count + success_count;
+            let total_interactions = i64::from(miss_count) + i64::from(success_count);
+            let free_misses = (i64::from(self.config.free_misses_for_new_cards()) - total_interactions).max(0) as u32;
+            let width = self.card_width_for(&front);
+            let x = self.pick_spawn_x(width);
+            let preview_seconds = self.config.answer_preview_seconds;
+            let preview_until = (total_interactions == 0 && preview_seconds > 0.0).then_some(preview_seconds);
             self.cards.push(Card {
                 id: self.next_card_id,
                 raw_front,
                 raw_back,
                 front,
                 back,
-                x: self.rng.random_range(0.0..(self.width - CARD_WIDTH)),
+                x,
                 y: 0.0,
                 flipped: false,
                 time_since_flipped: None,
-                free_misses: (2 - total_interactions).max(0),
+                free_misses,
+                hint,
+                age: 0.0,
+                width,
+                answered_alternatives: Vec::new(),
+                category,
+                points,
+                preview_until,
             });
+            self.events.push(GameEvent::CardSpawned { id: self.next_card_id });
             self.next_card_id += 1;
         }
     }
 
     fn replenish_deck(&mut self) {
-        let available_cards = self.get_available_cards_data();
-
-        let mut new_deck = Vec::new();
-        for (front, back) in available_cards {
-            let success_count = self.card_success_counts.get(front).cloned().unwrap_or(0);
-            let num_duplicates = (DECK_CARD_DUPLICATES as i32 - success_count as i32).max(1) as u32;
+        self.unlocked_cards_count = self.unlocked_cards_data().len();
+        // With every card excluded there is no round to start
+        if self.get_available_cards_data().is_empty() {
+            self.card_deck.clear();
+            return;
+        }
+        self.deck_round += 1;
+        let mut due_cards = Vec::new();
+        let mut later_cards = Vec::new();
+        for card in self.get_available_cards_data() {
+            let num_duplicates = self.deck_duplicates_for(&card.front);
+            let target = if self.is_due(&card.front) { &mut due_cards } else { &mut later_cards };
             for _ in 0..num_duplicates {
-                new_deck.push((front.clone(), back.clone()));
+                target.push(card.clone());
             }
         }
 
-        self.unlocked_cards_count = available_cards.len();
-        new_deck.shuffle(&mut self.rng);
+        due_cards.shuffle(&mut self.rng);
+        later_cards.shuffle(&mut self.rng);
+
+        // spawn_card pops from the back, so due cards are drawn first
+        later_cards.extend(due_cards);
+        self.card_deck = later_cards;
+    }
 
-        self.card_deck = new_deck;
+    /// Returns the events collected since the last call and clears them.
+    pub fn drain_events(&mut self) -> JsValue {
+        let events = std::mem::take(&mut self.events);
+        serde_wasm_bindgen::to_value(&events).unwrap()
     }
 
     pub fn get_cards(&self) -> JsValue {
         serde_wasm_bindgen::to_value(&self.cards).unwrap()
     }
 
-    pub fn get_cards_for_render(&self) -> JsValue {
-        let render_cards: Vec<RenderableCard> = self.cards
-            .iter()
-            .map(|card| RenderableCard {
-                id: card.id,
-                front: &card.front,
-                back: &card.back,
-                x: card.x,
-                y: card.y,
-                flipped: card.flipped,
-                free_misses: card.free_misses,
-            })
-            .collect();
-        serde_wasm_bindgen::to_value(&render_cards).unwrap()
+    /// Falling cards ordered by `y`, so cards closer to the bottom are drawn
+    /// on top. Flipped cards showing a missed answer are only included when
+    /// `include_flipped` is set.
+    pub fn get_cards_for_render(&self, include_flipped: bool) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.cards_for_render(include_flipped)).unwrap()
+    }
+
+    /// The falling or flipped card with this id in the same shape as
+    /// `get_cards_for_render`, or null if there is none.
+    pub fn get_card_by_id(&self, id: u32) -> JsValue {
+        match self.cards.iter().find(|card| card.id == id) {
+            Some(card) => serde_wasm_bindgen::to_value(&RenderableCard::from(card)).unwrap(),
+            None => JsValue::NULL,
+        }
+    }
+
+    /// Bundles the per-frame HUD values into one object so the render loop
+    /// only has to cross the JS/WASM boundary once.
+    pub fn get_game_state(&self) -> JsValue {
+        let state = GameState {
+            score: self.get_score(),
+            health: self.get_health(),
+            max_health: self.get_max_health(),
+            game_over: self.is_game_over(),
+            paused: self.is_paused(),
+            spawn_interval: self.card_spawn_interval,
+            card_speed: self.card_speed,
+            unlocked_count: self.unlocked_cards_data().len(),
+        };
+        serde_wasm_bindgen::to_value(&state).unwrap()
+    }
+
+    /// Switches the game mode mid-session. Falling cards are re-oriented for
+    /// Normal and Reverse; switching to Both keeps their current orientation.
+    /// Flipped cards keep showing what was missed.
+    pub fn set_mode(&mut self, mode: GameMode) {
+        self.record(ReplayAction::Mode { mode });
+        self.mode = mode;
+        if mode == GameMode::Both {
+            return;
+        }
+        for card in self.cards.iter_mut().filter(|card| !card.flipped) {
+            let (front, back) = if mode == GameMode::Reverse {
+                (card.raw_back.clone(), card.raw_front.clone())
+            } else {
+                (card.raw_front.clone(), card.raw_back.clone())
+            };
+            card.front = front;
+            card.back = back;
+        }
+    }
+
+    pub fn get_mode(&self) -> GameMode {
+        self.mode
+    }
+
+    /// Up to `n` cards that will be spawned next, soonest first. In Both mode
+    /// the side shown is only decided at spawn, so cards are given as stored.
+    pub fn peek_next_cards(&self, n: usize) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.upcoming_cards(n)).unwrap()
+    }
+
+    /// The card the next spawn will draw, as stored in the deck, or null if
+    /// the deck is empty and has to be reshuffled first.
+    pub fn get_next_card_preview(&self) -> JsValue {
+        match self.next_deck_index() {
+            Some(index) => {
+                let card = &self.card_deck[index];
+                serde_wasm_bindgen::to_value(&CardPreview { raw_front: &card.front, raw_back: &card.back }).unwrap()
+            }
+            None => JsValue::NULL,
+        }
     }
 
     pub fn get_id(&self) -> u32 {
@@ -411,20 +2088,19 @@ impl Game {
 
     pub fn get_all_cards_for_display(&self) -> JsValue {
         let all_cards_data = &self.card_data;
-        let num_unlocked_cards = (INITIAL_UNLOCKED_CARDS
-            + (self.score / SCORE_PER_CARD_UNLOCK) as usize * CARDS_PER_UNLOCK)
-            .min(all_cards_data.len());
+        let num_unlocked_cards = self.unlocked_cards_data().len();
         let cards_for_display: Vec<CardForDisplay> = match self.mode {
             GameMode::Both => all_cards_data
                 .iter()
                 .enumerate()
-                .flat_map(|(i, (raw_front, raw_back))| {
+                .flat_map(|(i, CustomCard { front: raw_front, back: raw_back, tags, .. })| {
                     let success_count = self.card_success_counts.get(raw_front).cloned().unwrap_or(0);
                     let miss_count = self.card_miss_counts.get(raw_front).cloned().unwrap_or(0);
                     let is_unlocked = i < num_unlocked_cards;
+                    let is_excluded = self.excluded.contains(raw_front);
                     [
-                        CardForDisplay { raw_front, raw_back, front: raw_front, back: raw_back, success_count, miss_count, is_unlocked },
-                        CardForDisplay { raw_front, raw_back, front: raw_back, back: raw_front, success_count, miss_count, is_unlocked },
+                        CardForDisplay { raw_front, raw_back, front: raw_front, back: raw_back, tags, success_count, miss_count, is_unlocked, is_excluded },
+                        CardForDisplay { raw_front, raw_back, front: raw_back, back: raw_front, tags, success_count, miss_count, is_unlocked, is_excluded },
                     ]
                 })
                 .collect(),
@@ -433,12 +2109,13 @@ impl Game {
                 all_cards_data
                     .iter()
                     .enumerate()
-                    .map(|(i, (raw_front, raw_back))| {
+                    .map(|(i, CustomCard { front: raw_front, back: raw_back, tags, .. })| {
                         let (front, back) = if reverse { (raw_back.as_str(), raw_front.as_str()) } else { (raw_front.as_str(), raw_back.as_str()) };
                         let success_count = self.card_success_counts.get(raw_front).cloned().unwrap_or(0);
                         let miss_count = self.card_miss_counts.get(raw_front).cloned().unwrap_or(0);
                         let is_unlocked = i < num_unlocked_cards;
-                        CardForDisplay { raw_front, raw_back, front, back, success_count, miss_count, is_unlocked }
+                        let is_excluded = self.excluded.contains(raw_front);
+                        CardForDisplay { raw_front, raw_back, front, back, tags, success_count, miss_count, is_unlocked, is_excluded }
                     })
                     .collect()
             }
@@ -446,22 +2123,176 @@ impl Game {
         serde_wasm_bindgen::to_value(&cards_for_display).unwrap()
     }
 
-    pub fn get_missed_cards(&self) -> JsValue {
-        serde_wasm_bindgen::to_value(&self.missed_cards).unwrap()
+    /// Takes a card out of rotation by its raw front. It stops spawning, but
+    /// stays listed in `get_all_cards_for_display` with `is_excluded` set.
+    pub fn exclude_card(&mut self, raw_front: &str) {
+        self.record(ReplayAction::Exclude { raw_front: raw_front.to_string() });
+        self.excluded.insert(raw_front.to_string());
+        self.card_deck.retain(|card| card.front != raw_front);
     }
 
-    pub fn get_card_miss_counts(&self) -> JsValue {
+    /// Puts a card excluded with `exclude_card` back into rotation by
+    /// shuffling its copies into the current deck.
+    pub fn include_card(&mut self, raw_front: &str) {
+        self.record(ReplayAction::Include { raw_front: raw_front.to_string() });
+        if !self.excluded.remove(raw_front) {
+            return;
+        }
+        let Some(card) = self.get_available_cards_data().into_iter().find(|card| card.front == raw_front).cloned() else {
+            return;
+        };
+        for _ in 0..self.deck_duplicates_for(raw_front) {
+            let index = self.rng.random_range(0..=self.card_deck.len());
+            self.card_deck.insert(index, card.clone());
+        }
+    }
+
+    pub fn get_missed_cards(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.missed_cards).unwrap()
+    }
+
+    /// The cards missed this game, once each, with their `miss_count` and
+    /// `success_count` over all games. Most missed first.
+    pub fn get_missed_cards_summary(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.missed_card_counts()).unwrap()
+    }
+
+    /// Like `get_missed_cards`, but with one entry per card and a
+    /// `times_missed` count, most missed first.
+    pub fn get_missed_cards_deduplicated(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.missed_card_summaries()).unwrap()
+    }
+
+    pub fn get_card_miss_counts(&self) -> JsValue {
         serde_wasm_bindgen::to_value(&self.card_miss_counts).unwrap()
     }
 
+    /// Restores per-card success and miss counts saved from an earlier session,
+    /// e.g. from `get_card_success_counts` and `get_card_miss_counts`. Well
+    /// known cards then get fewer copies in the deck and no free misses.
+    pub fn load_stats(&mut self, success: JsValue, miss: JsValue) -> Result<(), JsValue> {
+        let success_counts: HashMap<String, u32> = serde_wasm_bindgen::from_value(success)?;
+        let miss_counts: HashMap<String, u32> = serde_wasm_bindgen::from_value(miss)?;
+        self.apply_stats(success_counts, miss_counts);
+        Ok(())
+    }
+
+    /// Points scored this game per card category. Cards without a category
+    /// count towards "default".
+    pub fn get_category_scores(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.category_scores).unwrap()
+    }
+
     pub fn get_card_success_counts(&self) -> JsValue {
         serde_wasm_bindgen::to_value(&self.card_success_counts).unwrap()
     }
 
+    /// Seconds played this game, not counting pauses or time after game over.
+    pub fn get_play_time(&self) -> f64 {
+        self.elapsed
+    }
+
+    /// Average seconds from spawn to clear over the cards cleared this game,
+    /// or 0.0 if none were cleared yet.
+    pub fn get_average_response_time(&self) -> f64 {
+        if self.response_count == 0 {
+            return 0.0;
+        }
+        self.total_response_time / self.response_count as f64
+    }
+
+    /// Average seconds from spawn to clear per raw_front, for every card that
+    /// was cleared at least once. Kept across restarts like the answer counts.
+    pub fn get_card_latencies(&self) -> JsValue {
+        let latencies: HashMap<&str, f64> = self
+            .card_latencies
+            .iter()
+            .map(|(raw_front, (total, count))| (raw_front.as_str(), total / *count as f64))
+            .collect();
+        serde_wasm_bindgen::to_value(&latencies).unwrap()
+    }
+
+    /// Share of correct answers among all answered and missed cards, from 0.0
+    /// to 1.0. Returns 0.0 before anything has been answered.
+    pub fn get_accuracy(&self) -> f64 {
+        let total = self.get_total_answered();
+        if total == 0 {
+            return 0.0;
+        }
+        let successes: u32 = self.card_success_counts.values().sum();
+        successes as f64 / total as f64
+    }
+
+    /// Total number of correct answers and misses, kept across restarts.
+    pub fn get_total_answered(&self) -> u32 {
+        self.card_success_counts.values().sum::<u32>() + self.card_miss_counts.values().sum::<u32>()
+    }
+
+    /// Summary for the end-of-game screen. Answer totals are cumulative, the
+    /// streaks only cover the current game.
+    pub fn get_statistics(&self) -> JsValue {
+        let stats = Statistics {
+            total_answers: self.get_total_answered(),
+            total_misses: self.card_miss_counts.values().sum(),
+            accuracy_percent: self.get_accuracy() * 100.0,
+            current_streak: self.current_streak,
+            best_streak: self.best_streak,
+        };
+        serde_wasm_bindgen::to_value(&stats).unwrap()
+    }
+
+    /// Progress of every unlocked card. A card counts as mastered once it has
+    /// been answered correctly as often as it is duplicated in a fresh deck.
+    pub fn get_deck_progress(&self) -> JsValue {
+        let cards: Vec<CardProgress> = self
+            .get_available_cards_data()
+            .iter()
+            .map(|card| {
+                let success_count = self.card_success_counts.get(&card.front).cloned().unwrap_or(0);
+                CardProgress {
+                    raw_front: card.front.clone(),
+                    box_level: self.card_boxes.get(&card.front).cloned().unwrap_or(0),
+                    duplicates: self.deck_duplicates_for(&card.front),
+                    success_count,
+                    miss_count: self.card_miss_counts.get(&card.front).cloned().unwrap_or(0),
+                    mastered: success_count >= self.config.deck.deck_card_duplicates(),
+                }
+            })
+            .collect();
+        let mastered = cards.iter().filter(|card| card.mastered).count();
+        let progress = DeckProgress { in_progress: cards.len() - mastered, mastered, cards };
+        serde_wasm_bindgen::to_value(&progress).unwrap()
+    }
+
+    /// Leitner box level (0 to 4) per card front, for cards that have been seen.
+    pub fn get_card_boxes(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.card_boxes).unwrap()
+    }
+
+    /// Spaced repetition schedule per card front, for debugging views.
+    pub fn get_card_schedules(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.card_schedules).unwrap()
+    }
+
+    /// Current falling speed in pixels per second, including the speed multiplier.
+    pub fn get_card_speed(&self) -> f64 {
+        self.card_speed
+    }
+
+    /// Current seconds between card spawns.
+    pub fn get_spawn_interval(&self) -> f64 {
+        self.card_spawn_interval
+    }
+
     pub fn get_score(&self) -> i32 {
         self.score
     }
 
+    /// Score without the combo multiplier, which decides when cards unlock.
+    pub fn get_raw_score(&self) -> i32 {
+        self.raw_score
+    }
+
     pub fn get_health(&self) -> i32 {
         self.health
     }
@@ -470,12 +2301,53 @@ impl Game {
         self.max_health
     }
 
+    /// Seconds left in a timed game, or `undefined` when the game is untimed.
+    pub fn get_time_remaining(&self) -> Option<f64> {
+        self.time_remaining
+    }
+
+    /// Number of consecutive correct answers since the last missed card.
+    pub fn get_combo_streak(&self) -> u32 {
+        self.combo_streak
+    }
+
+    /// Multiplier applied to the points of the next correct answer.
+    pub fn get_combo_multiplier(&self) -> u32 {
+        (1 + self.combo_streak / COMBO_STREAK_PER_MULTIPLIER).min(MAX_COMBO_MULTIPLIER)
+    }
+
+    /// The combo for the UI: streak, multiplier and the seconds left before
+    /// the streak resets without another correct answer.
+    pub fn get_combo(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.combo_state()).unwrap()
+    }
+
+    pub fn is_practice(&self) -> bool {
+        self.config.practice
+    }
+
+    /// Alias of `is_practice`.
+    pub fn is_zen(&self) -> bool {
+        self.is_practice()
+    }
+
+    /// Whether the spawn interval and card speed ignore the score.
+    pub fn is_constant_difficulty(&self) -> bool {
+        self.config.constant_difficulty
+    }
+
+    pub fn is_hardcore(&self) -> bool {
+        self.config.hardcore
+    }
+
     pub fn is_game_over(&self) -> bool {
         self.game_over
     }
 
+    /// Pauses the game and drops any answers still waiting in the queue.
     pub fn pause(&mut self) {
         self.paused = true;
+        self.queued_answers.clear();
     }
 
     pub fn resume(&mut self) {
@@ -490,71 +2362,474 @@ impl Game {
         let card_data = self.card_data.clone();
         let card_success_counts = self.card_success_counts.clone();
         let card_miss_counts = self.card_miss_counts.clone();
+        let card_boxes = self.card_boxes.clone();
+        let card_schedules = self.card_schedules.clone();
+        let card_latencies = self.card_latencies.clone();
+        let deck_round = self.deck_round;
+        let excluded = std::mem::take(&mut self.excluded);
         *self = Self {
             width: self.width,
             height: self.height,
             rng_seed: self.rng_seed,
             game_id: self.game_id,
             mode: self.mode,
+            health: self.config.initial_health(),
             max_health: self.max_health,
+            card_spawn_interval: self.config.initial_spawn_interval(),
+            card_speed: self.config.initial_card_speed() * self.speed_multiplier,
+            time_remaining: self.config.time_limit,
             speed_multiplier: self.speed_multiplier,
+            fuzzy_tolerance: self.fuzzy_tolerance,
+            config: self.config.clone(),
+            replay_config: self.config.clone(),
+            replay_speed_multiplier: self.speed_multiplier,
             rng: ChaCha8Rng::seed_from_u64(self.rng_seed),
             ..Self::default()
         };
         self.card_data = card_data;
         self.card_success_counts = card_success_counts;
         self.card_miss_counts = card_miss_counts;
-        self.card_speed *= self.speed_multiplier;
-        self.spawn_card();
+        self.card_boxes = card_boxes;
+        self.card_schedules = card_schedules;
+        self.card_latencies = card_latencies;
+        self.deck_round = deck_round;
+        self.excluded = excluded;
+        if self.config.autostart() {
+            self.spawn_card();
+        }
+    }
+
+    /// Sets how many character edits an answer may be away from a correct
+    /// alternative and still count. 0 (the default) requires an exact match.
+    pub fn set_fuzzy_tolerance(&mut self, max_edits: u32) {
+        self.fuzzy_tolerance = max_edits;
+    }
+
+    pub fn get_fuzzy_tolerance(&self) -> u32 {
+        self.fuzzy_tolerance
+    }
+
+    /// Sets the chance, from 0 to 1, that a spawn becomes a burst of two or
+    /// three cards at once. Bursts still respect the card limit.
+    pub fn set_burst_probability(&mut self, p: f64) {
+        self.record(ReplayAction::BurstProbability { p });
+        self.config.burst_probability = p.clamp(0.0, 1.0);
+    }
+
+    /// Changes the speed multiplier mid-game. The card speed keeps its
+    /// score-based part and is scaled by the new multiplier, at least 0.1.
+    pub fn set_speed_multiplier(&mut self, multiplier: f64) {
+        self.record(ReplayAction::SpeedMultiplier { multiplier });
+        self.speed_multiplier = multiplier.max(MIN_SPEED_MULTIPLIER);
+        self.card_speed = self.score_card_speed() * self.speed_multiplier;
+    }
+
+    /// Enables blitz mode, where cards speed up as they near the bottom and
+    /// arrive there `1 + factor` times as fast as they started. 0 turns it off.
+    pub fn set_blitz_acceleration(&mut self, factor: f64) {
+        self.record(ReplayAction::BlitzAcceleration { factor });
+        self.config.blitz_acceleration = factor.max(0.0);
+    }
+
+    /// Makes cards flip `margin` pixels above the bottom of the play area.
+    pub fn set_bottom_margin(&mut self, margin: f64) {
+        self.record(ReplayAction::BottomMargin { margin });
+        self.config.bottom_margin = margin.max(0.0);
+    }
+
+    /// Limits how many cards may fall at once, however high the score gets.
+    pub fn set_max_cards_cap(&mut self, cap: usize) {
+        self.record(ReplayAction::MaxCardsCap { cap });
+        self.config.max_cards_cap = Some(cap);
+    }
+
+    /// Sets how many seconds a missed card keeps showing its answer before it
+    /// disappears. Values below 0.1 are raised to 0.1.
+    pub fn set_flip_display_seconds(&mut self, secs: f64) {
+        self.record(ReplayAction::FlipDisplaySeconds { secs });
+        self.config.flip_display_seconds = Some(secs.max(MIN_FLIP_DISPLAY_SECONDS));
+    }
+
+    pub fn get_flip_display_seconds(&self) -> f64 {
+        self.config.flip_display_seconds()
+    }
+
+    /// Slows falling cards to half speed for `duration` seconds. Activating it
+    /// again while active restarts the timer rather than stacking. A combo of
+    /// `SLOWMO_COMBO_THRESHOLD` correct answers also grants it.
+    pub fn activate_slowmo(&mut self, duration: f64) {
+        if self.game_over || self.paused {
+            return;
+        }
+        self.record(ReplayAction::Slowmo { duration });
+        self.slowmo_remaining = duration.max(0.0);
+    }
+
+    pub fn is_slowmo_active(&self) -> bool {
+        self.slowmo_remaining > 0.0
+    }
+
+    /// Alias of `is_slowmo_active`.
+    pub fn is_slow_mo_active(&self) -> bool {
+        self.is_slowmo_active()
+    }
+
+    /// Seconds of slow motion left, 0 when inactive.
+    pub fn get_slowmo_remaining(&self) -> f64 {
+        self.slowmo_remaining
+    }
+
+    /// Shuffles the cards left in the deck with the game's seeded rng, so the
+    /// new order is still reproducible.
+    pub fn reshuffle_deck(&mut self) {
+        self.record(ReplayAction::Reshuffle);
+        self.card_deck.shuffle(&mut self.rng);
+    }
+
+    /// Ends the game at once. Every falling card is flipped to reveal its
+    /// answer and counted as a miss, but no health is lost.
+    pub fn give_up(&mut self) {
+        if self.game_over {
+            return;
+        }
+        self.record(ReplayAction::GiveUp);
+        for card in self.cards.iter_mut().filter(|card| !card.flipped) {
+            card.flipped = true;
+            card.time_since_flipped = Some(0.0);
+            self.events.push(GameEvent::CardMissed { id: card.id });
+            *self.card_miss_counts.entry(card.raw_front.clone()).or_insert(0) += 1;
+            self.card_boxes.insert(card.raw_front.clone(), 0);
+            self.card_schedules.entry(card.raw_front.clone()).or_default().record_miss(self.deck_round);
+            self.missed_cards.push(card.clone());
+        }
+        self.combo_streak = 0;
+        self.current_streak = 0;
+        self.end_game();
+    }
+
+    /// Discards a falling card without touching health or the miss/success
+    /// counts. Only a limited number of skips are available per game.
+    pub fn skip_card(&mut self, id: u32) -> bool {
+        if self.game_over || self.paused || self.skips_remaining == 0 {
+            return false;
+        }
+        let Some(index) = self.cards.iter().position(|card| card.id == id && !card.flipped) else {
+            return false;
+        };
+        self.record(ReplayAction::Skip { id });
+        self.cards.remove(index);
+        self.hints_used.remove(&id);
+        self.skips_remaining -= 1;
+        true
+    }
+
+    /// Takes back the most recent miss if it happened within the last two
+    /// seconds: it no longer counts as missed, any heart it cost is restored,
+    /// and the card's Leitner box, schedule and the streaks are as before it.
+    pub fn undo_last_miss(&mut self) -> bool {
+        if self.game_over {
+            return false;
+        }
+        let Some(last_miss) = self.last_miss.take_if(|miss| miss.age <= UNDO_MISS_WINDOW_SECONDS) else {
+            return false;
+        };
+        self.record(ReplayAction::UndoMiss);
+        if let Some(index) = self.missed_cards.iter().rposition(|card| card.raw_front == last_miss.raw_front) {
+            self.missed_cards.remove(index);
+        }
+        if let Some(miss_count) = self.card_miss_counts.get_mut(&last_miss.raw_front) {
+            *miss_count = miss_count.saturating_sub(1);
+        }
+        if last_miss.cost_health && self.health < self.max_health {
+            self.health += 1;
+            self.events.push(GameEvent::HeartGained);
+        }
+        match last_miss.previous_box {
+            Some(box_level) => self.card_boxes.insert(last_miss.raw_front.clone(), box_level),
+            None => self.card_boxes.remove(&last_miss.raw_front),
+        };
+        match last_miss.previous_schedule {
+            Some(schedule) => self.card_schedules.insert(last_miss.raw_front.clone(), schedule),
+            None => self.card_schedules.remove(&last_miss.raw_front),
+        };
+        // Answers given since the miss continue the streaks it broke
+        self.combo_streak += last_miss.previous_combo_streak;
+        self.current_streak += last_miss.previous_streak;
+        self.best_streak = self.best_streak.max(self.current_streak);
+        true
+    }
+
+    /// Reveals the first letter of a falling card's answer once the card has
+    /// been falling for a few seconds. The card then awards no points when
+    /// cleared. Returns nothing for unknown, flipped or too recent cards.
+    pub fn request_hint(&mut self, card_id: u32) -> Option<String> {
+        if self.game_over || self.paused {
+            return None;
+        }
+        let card = self.cards.iter().find(|card| card.id == card_id && !card.flipped && card.age >= HINT_DELAY_SECONDS)?;
+        let masked = mask_answer(&card.back);
+        self.record(ReplayAction::Hint { id: card_id });
+        self.hints_used.insert(card_id);
+        Some(masked)
+    }
+
+    /// Short hex digest of the game id, seed, score and the cards answered so
+    /// far. Replaying the same game gives the same signature. This is not
+    /// cryptographically secure; it only deters casual score tampering.
+    pub fn score_signature(&self) -> String {
+        let hash = fnv1a(self.answer_digest, &self.game_id.to_le_bytes());
+        let hash = fnv1a(hash, &self.rng_seed.to_le_bytes());
+        let hash = fnv1a(hash, &self.score.to_le_bytes());
+        format!("{:016x}", hash)
+    }
+
+    /// The difficulty the game was configured with, `Normal` by default.
+    pub fn get_difficulty(&self) -> Difficulty {
+        self.config.difficulty
+    }
+
+    /// Height of every card in pixels, for sizing the card elements.
+    pub fn get_card_height(&self) -> f64 {
+        self.config.card_height()
+    }
+
+    /// How many cards are unlocked out of the deck's total, and how many more
+    /// points the next unlock needs (null once everything is unlocked).
+    pub fn get_unlock_progress(&self) -> JsValue {
+        let serializer = serde_wasm_bindgen::Serializer::new().serialize_missing_as_null(true);
+        self.unlock_progress().serialize(&serializer).unwrap()
+    }
+
+    /// Number of cards on screen, falling or flipped.
+    pub fn get_active_card_count(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Number of cards left to draw before the deck is reshuffled.
+    pub fn get_deck_remaining(&self) -> usize {
+        self.card_deck.len()
+    }
+
+    pub fn get_skips_remaining(&self) -> u32 {
+        self.skips_remaining
+    }
+
+    /// Buffers an answer to be checked at the start of the next tick, before
+    /// cards move. Queued answers are resolved in order and dropped when the
+    /// game is paused or over.
+    pub fn queue_answer(&mut self, answer: &str) {
+        if !self.game_over && !self.paused {
+            self.queued_answers.push(answer.to_string());
+        }
     }
 
     pub fn submit_answer(&mut self, answer: &str) -> bool {
+        !self.resolve_answer(answer, self.fuzzy_tolerance).removed_ids.is_empty()
+    }
+
+    /// Like `submit_answer`, but reports the ids of every cleared card, the new
+    /// score and whether the answer unlocked new cards.
+    pub fn submit_answer_detailed(&mut self, answer: &str) -> JsValue {
+        let result = self.resolve_answer(answer, self.fuzzy_tolerance);
+        serde_wasm_bindgen::to_value(&result).unwrap()
+    }
+
+    /// Like `submit_answer`, but accepts answers up to `max_distance` edits away
+    /// from an alternative for this submission only. Short alternatives allow
+    /// proportionally fewer edits.
+    pub fn submit_answer_fuzzy(&mut self, answer: &str, max_distance: u32) -> bool {
+        !self.resolve_answer(answer, max_distance).removed_ids.is_empty()
+    }
+
+    /// Multiple-choice options for a falling card: its answer plus up to
+    /// `n - 1` answers of other unlocked cards, shuffled with the seeded rng.
+    pub fn get_choices_for_card(&mut self, id: u32, n: usize) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.choices_for_card(id, n)).unwrap()
+    }
+
+    /// Answers the card with this id with one of its choices. Like
+    /// `submit_answer`, a wrong choice costs nothing and clears nothing.
+    pub fn submit_choice(&mut self, id: u32, chosen: &str) -> bool {
         if self.game_over || self.paused {
             return false;
         }
-        let normalized_answer = normalize_string(answer);
+        self.record(ReplayAction::Choice { id, chosen: chosen.to_string() });
+        let options = self.config.normalize_options();
+        let normalized_choice = normalize_with(chosen, options);
+        let Some(index) = self.cards.iter().position(|card| {
+            card.id == id
+                && !card.flipped
+                && (normalize_with(&card.back, options) == normalized_choice
+                    || card.back.split('/').any(|ans| answer_matches(ans, &normalized_choice, 0, options)))
+        }) else {
+            return false;
+        };
+        let card = self.cards.remove(index);
+        self.handle_correct_answer(&[card]);
+        true
+    }
+
+    // The card's back and up to `n - 1` distinct other backs of playable
+    // cards, in seeded random order. Empty for unknown or flipped cards.
+    fn choices_for_card(&mut self, id: u32, n: usize) -> Vec<String> {
+        let Some(card) = self.cards.iter().find(|card| card.id == id && !card.flipped) else {
+            return Vec::new();
+        };
+        let correct = card.back.clone();
+        // Distractors come from the same side of the other cards
+        let reversed = card.back != card.raw_back;
+        self.record(ReplayAction::Choices { id, n });
+        let mut distractors: Vec<String> = Vec::new();
+        for other in self.get_available_cards_data() {
+            let side = if reversed { &other.front } else { &other.back };
+            if *side != correct && !distractors.contains(side) {
+                distractors.push(side.clone());
+            }
+        }
+        distractors.shuffle(&mut self.rng);
+        distractors.truncate(n.saturating_sub(1));
+        distractors.push(correct);
+        distractors.shuffle(&mut self.rng);
+        distractors
+    }
+
+    fn resolve_answer(&mut self, answer: &str, tolerance: u32) -> SubmitResult {
+        if self.game_over || self.paused {
+            return SubmitResult { score: self.score, ..SubmitResult::default() };
+        }
+        self.record(ReplayAction::Submit { answer: answer.to_string(), tolerance });
+        let options = self.config.normalize_options();
+        let normalized_answer = normalize_with(answer, options);
+
+        let require_all_answers = self.config.require_all_answers;
+        if require_all_answers {
+            for card in self.cards.iter_mut().filter(|card| !card.flipped) {
+                for (index, ans) in card.back.split('/').enumerate() {
+                    if !card.answered_alternatives.contains(&index) && answer_matches(ans, &normalized_answer, tolerance, options) {
+                        card.answered_alternatives.push(index);
+                    }
+                }
+            }
+        }
 
         let (removed_cards, kept_cards): (Vec<Card>, Vec<Card>) = self.cards.drain(..).partition(|card| {
-            !card.flipped && card.back.split('/').any(|ans| normalize_string(ans.trim()) == normalized_answer)
+            !card.flipped && if require_all_answers {
+                card.answered_alternatives.len() == card.back.split('/').count()
+            } else {
+                card.back.split('/').any(|ans| answer_matches(ans, &normalized_answer, tolerance, options))
+            }
         });
 
         self.cards = kept_cards;
-        
-        let correct = !removed_cards.is_empty();
-        if correct {
-            self.handle_correct_answer(&removed_cards);
+
+        let unlocked = !removed_cards.is_empty() && self.handle_correct_answer(&removed_cards);
+        SubmitResult {
+            removed_ids: removed_cards.iter().map(|card| card.id).collect(),
+            score: self.score,
+            unlocked,
         }
-        correct
     }
 
-    fn handle_correct_answer(&mut self, removed_cards: &[Card]) {
-        let removed_count = removed_cards.len() as i32;
-        self.score += removed_count;
-        self.score_since_last_heart += removed_count;
+    // Applies scoring, unlocking and difficulty for cleared cards. Returns
+    // whether new cards were unlocked.
+    fn handle_correct_answer(&mut self, removed_cards: &[Card]) -> bool {
+        let combo_multiplier = self.get_combo_multiplier() as i32;
+        let hinted: HashSet<u32> = removed_cards.iter().map(|card| card.id).filter(|id| self.hints_used.contains(id)).collect();
+        self.hints_used.retain(|id| !hinted.contains(id));
+        let mut points = 0;
+        for card in removed_cards.iter().filter(|card| !hinted.contains(&card.id)) {
+            let raw_points = card.points.unwrap_or(DEFAULT_CARD_POINTS) as i32;
+            self.raw_score += raw_points;
+            let card_points = raw_points * combo_multiplier;
+            points += card_points;
+            let category = card.category.as_deref().unwrap_or(DEFAULT_CATEGORY);
+            *self.category_scores.entry(category.to_string()).or_insert(0) += card_points;
+        }
+        self.score += points;
+        self.score_since_last_heart += points;
+        self.combo_streak += 1;
+        let threshold = self.config.slowmo_combo_threshold();
+        if threshold > 0 && self.combo_streak.is_multiple_of(threshold) {
+            self.slowmo_remaining = self.slowmo_remaining.max(SLOWMO_COMBO_SECONDS);
+        }
+        self.time_since_last_correct = 0.0;
+        self.current_streak += 1;
+        self.best_streak = self.best_streak.max(self.current_streak);
 
         for card in removed_cards {
+            self.total_response_time += card.age;
+            self.response_count += 1;
+            let (total, count) = self.card_latencies.entry(card.raw_front.clone()).or_insert((0.0, 0));
+            *total += card.age;
+            *count += 1;
+            // The separator keeps "ab" + "c" apart from "a" + "bc"
+            self.answer_digest = fnv1a(fnv1a(self.answer_digest, card.raw_front.as_bytes()), &[0]);
             let count = self.card_success_counts.entry(card.raw_front.clone()).or_insert(0);
             *count += 1;
+            let box_level = self.card_boxes.entry(card.raw_front.clone()).or_insert(0);
+            *box_level = (*box_level + 1).min(MAX_LEITNER_BOX);
+            self.card_schedules.entry(card.raw_front.clone()).or_default().record_success(self.deck_round);
+            self.events.push(GameEvent::CardCleared { id: card.id });
         }
 
         // Check if new cards were unlocked and replenish deck if so
-        let num_unlocked_cards = self.get_available_cards_data().len();
-        if num_unlocked_cards > self.unlocked_cards_count {
+        let num_unlocked_cards = self.unlocked_cards_data().len();
+        let unlocked = num_unlocked_cards > self.unlocked_cards_count;
+        if unlocked {
+            self.events.push(GameEvent::CardsUnlocked { count: num_unlocked_cards - self.unlocked_cards_count });
             self.replenish_deck();
         }
 
         // Update difficulty
-        self.card_spawn_interval = (INITIAL_SPAWN_INTERVAL
-            - (self.score / SCORE_PER_SPAWN_INTERVAL_DECREASE) as f64 * SPAWN_INTERVAL_DECREASE)
-            .max(MIN_SPAWN_INTERVAL);
-        self.card_speed = (INITIAL_CARD_SPEED + (self.score as f64 * CARD_SPEED_INCREASE_PER_SCORE)) * self.speed_multiplier;
+        if !self.config.constant_difficulty {
+            self.card_spawn_interval = (self.config.initial_spawn_interval()
+                - (self.score / self.config.score_per_spawn_interval_decrease()) as f64 * SPAWN_INTERVAL_DECREASE)
+                .max(self.config.min_spawn_interval());
+            self.card_speed = self.score_card_speed() * self.speed_multiplier;
+        }
 
         // Update health
-        let hearts_to_gain = self.score_since_last_heart / SCORE_PER_HEART;
+        let score_per_heart = self.config.score_per_heart();
+        let hearts_to_gain = self.score_since_last_heart / score_per_heart;
         if hearts_to_gain > 0 {
+            let previous_health = self.health;
             self.health = (self.health + hearts_to_gain).min(self.max_health);
-            self.score_since_last_heart %= SCORE_PER_HEART;
+            self.score_since_last_heart %= score_per_heart;
+            for _ in previous_health..self.health {
+                self.events.push(GameEvent::HeartGained);
+            }
         }
+
+        unlocked
+    }
+
+    /// The missed cards, once each, as a deck that can be passed straight to
+    /// `Game::new` to drill them again.
+    pub fn to_missed_review_deck(&self) -> JsValue {
+        let deck: Vec<CustomCard> = self
+            .unique_missed_cards()
+            .into_iter()
+            .map(|card| {
+                self.card_data
+                    .iter()
+                    .find(|c| c.front == card.raw_front)
+                    .cloned()
+                    .unwrap_or_else(|| CustomCard {
+                        front: card.raw_front.clone(),
+                        back: card.raw_back.clone(),
+                        hint: card.hint.clone(),
+                        tags: vec![],
+                        category: card.category.clone(),
+                        points: card.points,
+                    })
+            })
+            .collect();
+        serde_wasm_bindgen::to_value(&deck).unwrap()
+    }
+
+    /// Alias of `to_missed_review_deck`.
+    pub fn build_review_deck(&self) -> JsValue {
+        self.to_missed_review_deck()
     }
 
     pub fn generate_anki_export(&self) -> String {
@@ -562,19 +2837,33 @@ impl Game {
             return "".to_string();
         }
 
-        let mut unique_cards = Vec::new();
-        let mut seen_fronts = HashSet::new();
-
-        for card in &self.missed_cards {
-            if seen_fronts.insert(&card.raw_front) {
-                unique_cards.push(card);
-            }
-        }
+        let unique_cards = self.unique_missed_cards();
 
         let mut content = "#separator:tab\n#html:true\n".to_string();
         let card_lines: Vec<String> = unique_cards
             .iter()
-            .map(|c| format!("{}\t{}", c.raw_front, c.raw_back))
+            .map(|c| format!("{}\t{}", escape_anki_field(&c.raw_front), escape_anki_field(&c.raw_back)))
+            .collect();
+
+        content.push_str(&card_lines.join("\n"));
+        content
+    }
+
+    /// Like `generate_anki_export`, with a third tag column `miss:N` holding
+    /// each card's miss count so the hardest cards can be filtered in Anki.
+    pub fn generate_anki_export_with_stats(&self) -> String {
+        if self.missed_cards.is_empty() {
+            return "".to_string();
+        }
+
+        let mut content = "#separator:tab\n#html:true\n#tags column:3\n".to_string();
+        let card_lines: Vec<String> = self
+            .unique_missed_cards()
+            .iter()
+            .map(|c| {
+                let misses = self.card_miss_counts.get(&c.raw_front).cloned().unwrap_or(0);
+                format!("{}\t{}\tmiss:{}", escape_anki_field(&c.raw_front), escape_anki_field(&c.raw_back), misses)
+            })
             .collect();
 
         content.push_str(&card_lines.join("\n"));
@@ -594,7 +2883,7 @@ mod tests {
 
     fn new_game_for_test(width: f64, height: f64, seed: u64, mode: GameMode, speed_multiplier: f64) -> Game {
         let deck_jsvalue = parse_deck(TEST_CARD_DATA);
-        Game::new(width, height, seed, mode, speed_multiplier, deck_jsvalue).unwrap()
+        Game::new(width, height, seed, mode, speed_multiplier, deck_jsvalue, JsValue::UNDEFINED).unwrap()
     }
 
     const TEST_CARD_DATA_LARGE: &str = "Card 1\tAnswer 1
@@ -616,7 +2905,7 @@ Card 15\tAnswer 15
 
     fn new_game_for_test_large(width: f64, height: f64, seed: u64, mode: GameMode, speed_multiplier: f64) -> Game {
         let deck_jsvalue = parse_deck(TEST_CARD_DATA_LARGE);
-        Game::new(width, height, seed, mode, speed_multiplier, deck_jsvalue).unwrap()
+        Game::new(width, height, seed, mode, speed_multiplier, deck_jsvalue, JsValue::UNDEFINED).unwrap()
     }
 
     #[test]
@@ -627,11 +2916,23 @@ Card 15\tAnswer 15
         assert_eq!(normalize_string("crème brûlée"), "creme brulee");
     }
 
+    #[test]
+    fn test_normalize_unicode_mode_keeps_non_latin_scripts() {
+        let mode = NormalizeOptions { mode: TextNormalization::Unicode, ..NormalizeOptions::default() };
+        assert_eq!(normalize_with("東京", mode), "東京");
+        assert_eq!(normalize_with("東京。", mode), "東京");
+        assert_eq!(normalize_with("مرحبا؟", mode), "مرحبا");
+        assert_eq!(normalize_with("  HeLlO, WoRlD!  ", mode), "hello world");
+        // Decomposed and precomposed forms compare equal after NFC
+        assert_eq!(normalize_with("cre\u{0300}me", mode), normalize_with("crème", mode));
+        assert_eq!(normalize_with("crème brûlée", NormalizeOptions::default()), "creme brulee");
+    }
+
     #[wasm_bindgen_test]
     fn test_submit_correct_answer() {
         let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
         game.cards = vec![
-            Card { id: 0, raw_front: "Q".to_string(), raw_back: "Answer1 / Answer2".to_string(), front: "Q".to_string(), back: "Answer1 / Answer2".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "Answer1 / Answer2".to_string(), front: "Q".to_string(), back: "Answer1 / Answer2".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
         ];
         assert_eq!(game.get_score(), 0);
         assert!(game.submit_answer("Answer2"));
@@ -644,7 +2945,7 @@ Card 15\tAnswer 15
     fn test_submit_incorrect_answer() {
         let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
         game.cards = vec![
-            Card { id: 0, raw_front: "Q".to_string(), raw_back: "Answer".to_string(), front: "Q".to_string(), back: "Answer".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "Answer".to_string(), front: "Q".to_string(), back: "Answer".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
         ];
         assert_eq!(game.get_score(), 0);
         assert!(!game.submit_answer("Wrong"));
@@ -657,7 +2958,7 @@ Card 15\tAnswer 15
     fn test_submit_answer_normalization() {
         let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
         game.cards = vec![
-            Card { id: 0, raw_front: "Q".to_string(), raw_back: "Answer One / How are you?".to_string(), front: "Q".to_string(), back: "Answer One / How are you?".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "Answer One / How are you?".to_string(), front: "Q".to_string(), back: "Answer One / How are you?".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
         ];
         assert!(game.submit_answer("  how ARE you?? "));
         let cards: Vec<Card> = serde_wasm_bindgen::from_value(game.get_cards()).unwrap();
@@ -668,7 +2969,7 @@ Card 15\tAnswer 15
     fn test_submit_answer_with_diacritics() {
         let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
         game.cards = vec![
-            Card { id: 0, raw_front: "Q".to_string(), raw_back: "crème brûlée".to_string(), front: "Q".to_string(), back: "crème brûlée".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "crème brûlée".to_string(), front: "Q".to_string(), back: "crème brûlée".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
         ];
         assert!(game.submit_answer("creme brulee"));
         let cards: Vec<Card> = serde_wasm_bindgen::from_value(game.get_cards()).unwrap();
@@ -679,9 +2980,9 @@ Card 15\tAnswer 15
     fn test_submit_answer_resolves_multiple_cards() {
         let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
         game.cards = vec![
-            Card { id: 0, raw_front: "Q1".to_string(), raw_back: "Answer".to_string(), front: "Q1".to_string(), back: "Answer".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
-            Card { id: 1, raw_front: "Q2".to_string(), raw_back: "Answer".to_string(), front: "Q2".to_string(), back: "Answer".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
-            Card { id: 2, raw_front: "Q3".to_string(), raw_back: "Different".to_string(), front: "Q3".to_string(), back: "Different".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
+            Card { id: 0, raw_front: "Q1".to_string(), raw_back: "Answer".to_string(), front: "Q1".to_string(), back: "Answer".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 1, raw_front: "Q2".to_string(), raw_back: "Answer".to_string(), front: "Q2".to_string(), back: "Answer".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 2, raw_front: "Q3".to_string(), raw_back: "Different".to_string(), front: "Q3".to_string(), back: "Different".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
         ];
         assert_eq!(game.get_score(), 0);
         assert!(game.submit_answer("answer"));
@@ -697,7 +2998,7 @@ Card 15\tAnswer 15
         let mut game = new_game_for_test(600.0, height, 0, GameMode::Normal, 1.0);
         game.card_miss_counts.insert("Q".to_string(), 2); // Mark card as not new
         game.cards = vec![
-            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
         ];
         // Prevent new cards from spawning during the test to isolate behavior
         game.card_spawn_interval = 1_000_000.0;
@@ -732,11 +3033,11 @@ Card 15\tAnswer 15
         let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
         game.health = 1; // set health low to test gain
         game.cards = vec![
-            Card { id: 0, raw_front: "Q1".to_string(), raw_back: "A".to_string(), front: "Q1".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
-            Card { id: 1, raw_front: "Q2".to_string(), raw_back: "A".to_string(), front: "Q2".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
-            Card { id: 2, raw_front: "Q3".to_string(), raw_back: "A".to_string(), front: "Q3".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
-            Card { id: 3, raw_front: "Q4".to_string(), raw_back: "A".to_string(), front: "Q4".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
-            Card { id: 4, raw_front: "Q5".to_string(), raw_back: "A".to_string(), front: "Q5".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
+            Card { id: 0, raw_front: "Q1".to_string(), raw_back: "A".to_string(), front: "Q1".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 1, raw_front: "Q2".to_string(), raw_back: "A".to_string(), front: "Q2".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 2, raw_front: "Q3".to_string(), raw_back: "A".to_string(), front: "Q3".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 3, raw_front: "Q4".to_string(), raw_back: "A".to_string(), front: "Q4".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 4, raw_front: "Q5".to_string(), raw_back: "A".to_string(), front: "Q5".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
         ];
         assert!(game.submit_answer("A"));
         assert_eq!(game.get_score(), 5);
@@ -751,7 +3052,7 @@ Card 15\tAnswer 15
         game.health = 1;
         game.card_miss_counts.insert("Q".to_string(), 2); // Mark card as not new
         game.cards = vec![
-            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
         ];
         // Prevent new cards from spawning during the test to isolate behavior
         game.card_spawn_interval = 1_000_000.0;
@@ -782,7 +3083,7 @@ Card 15\tAnswer 15
     fn test_pause_and_resume() {
         let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
         game.cards = vec![
-            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 10.0, flipped: false, time_since_flipped: None, free_misses: 0 },
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 10.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
         ];
 
         game.pause();
@@ -816,11 +3117,11 @@ Card 15\tAnswer 15
     fn test_difficulty_increases_with_score() {
         let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
         game.cards = vec![
-            Card { id: 0, raw_front: "Q1".to_string(), raw_back: "A".to_string(), front: "Q1".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
-            Card { id: 1, raw_front: "Q2".to_string(), raw_back: "A".to_string(), front: "Q2".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
-            Card { id: 2, raw_front: "Q3".to_string(), raw_back: "A".to_string(), front: "Q3".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
-            Card { id: 3, raw_front: "Q4".to_string(), raw_back: "A".to_string(), front: "Q4".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
-            Card { id: 4, raw_front: "Q5".to_string(), raw_back: "A".to_string(), front: "Q5".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
+            Card { id: 0, raw_front: "Q1".to_string(), raw_back: "A".to_string(), front: "Q1".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 1, raw_front: "Q2".to_string(), raw_back: "A".to_string(), front: "Q2".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 2, raw_front: "Q3".to_string(), raw_back: "A".to_string(), front: "Q3".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 3, raw_front: "Q4".to_string(), raw_back: "A".to_string(), front: "Q4".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 4, raw_front: "Q5".to_string(), raw_back: "A".to_string(), front: "Q5".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
         ];
         assert_eq!(game.card_spawn_interval, INITIAL_SPAWN_INTERVAL);
         assert_eq!(game.card_speed, INITIAL_CARD_SPEED);
@@ -842,8 +3143,9 @@ Card 15\tAnswer 15
 
         // Score enough points to unlock more cards (score 10)
         game.score = 9; // set score to 9 to be just before the threshold
+        game.raw_score = 9;
         game.cards = vec![
-            Card { id: game.next_card_id, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0 },
+            Card { id: game.next_card_id, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
         ];
         game.submit_answer("A");
         assert_eq!(game.get_score(), 10);
@@ -879,7 +3181,7 @@ Card 15\tAnswer 15
         }
         let cards: Vec<Card> = serde_wasm_bindgen::from_value(game.get_cards()).unwrap();
         
-        let original_fronts: HashSet<&str> = game.card_data.iter().map(|(f, _)| f.as_str()).collect();
+        let original_fronts: HashSet<&str> = game.card_data.iter().map(|c| c.front.as_str()).collect();
         let welsh_fronts = cards
             .iter()
             .filter(|c| original_fronts.contains(c.front.as_str()))
@@ -893,17 +3195,18 @@ Card 15\tAnswer 15
     #[wasm_bindgen_test]
     fn test_new_with_custom_deck_success() {
         let custom_cards = vec![
-            CustomCard { front: "Hello".to_string(), back: "World".to_string() },
-            CustomCard { front: "Foo".to_string(), back: "Bar".to_string() },
+            CustomCard { front: "Hello".to_string(), back: "World".to_string(), ..Default::default() },
+            CustomCard { front: "Foo".to_string(), back: "Bar".to_string(), ..Default::default() },
         ];
         let custom_deck_jsvalue = serde_wasm_bindgen::to_value(&custom_cards).unwrap();
         
-        let game_result = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, custom_deck_jsvalue);
+        let game_result = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, custom_deck_jsvalue, JsValue::UNDEFINED);
         assert!(game_result.is_ok());
         let game = game_result.unwrap();
         
         assert_eq!(game.card_data.len(), 2);
-        assert_eq!(game.card_data[0], ("Hello".to_string(), "World".to_string()));
+        assert_eq!(game.card_data[0].front, "Hello");
+        assert_eq!(game.card_data[0].back, "World");
         let cards: Vec<Card> = serde_wasm_bindgen::from_value(game.get_cards()).unwrap();
         assert_eq!(cards.len(), 1); // one card should be spawned on init
     }
@@ -913,25 +3216,26 @@ Card 15\tAnswer 15
         let custom_cards: Vec<CustomCard> = vec![];
         let custom_deck_jsvalue = serde_wasm_bindgen::to_value(&custom_cards).unwrap();
         
-        let game_result = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, custom_deck_jsvalue);
+        let game_result = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, custom_deck_jsvalue, JsValue::UNDEFINED);
         assert!(game_result.is_err());
     }
 
     #[wasm_bindgen_test]
     fn test_restart_preserves_custom_deck() {
         let custom_cards = vec![
-            CustomCard { front: "Test".to_string(), back: "Deck".to_string() },
+            CustomCard { front: "Test".to_string(), back: "Deck".to_string(), ..Default::default() },
         ];
         let custom_deck_jsvalue = serde_wasm_bindgen::to_value(&custom_cards).unwrap();
         
-        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, custom_deck_jsvalue).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, custom_deck_jsvalue, JsValue::UNDEFINED).unwrap();
         assert_eq!(game.card_data.len(), 1);
 
         game.score = 100; // change some state
         game.restart();
         
         assert_eq!(game.card_data.len(), 1);
-        assert_eq!(game.card_data[0], ("Test".to_string(), "Deck".to_string()));
+        assert_eq!(game.card_data[0].front, "Test");
+        assert_eq!(game.card_data[0].back, "Deck");
         assert_eq!(game.get_score(), 0);
     }
 
@@ -942,7 +3246,7 @@ Card 15\tAnswer 15
         // --- Test miss count ---
         let card_q = "Q".to_string();
         game.cards = vec![
-            Card { id: 0, raw_front: card_q.clone(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2 },
+            Card { id: 0, raw_front: card_q.clone(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
         ];
         game.card_spawn_interval = 1_000_000.0; // prevent more spawns
 
@@ -959,7 +3263,7 @@ Card 15\tAnswer 15
         // --- Test success count ---
         let card_q2 = "Q2".to_string();
         game.cards = vec![
-            Card { id: 1, raw_front: card_q2.clone(), raw_back: "A2".to_string(), front: "Q2".to_string(), back: "A2".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2 },
+            Card { id: 1, raw_front: card_q2.clone(), raw_back: "A2".to_string(), front: "Q2".to_string(), back: "A2".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
         ];
         
         assert!(game.submit_answer("A2"));
@@ -970,4 +3274,2472 @@ Card 15\tAnswer 15
         let miss_counts_after: HashMap<String, u32> = serde_wasm_bindgen::from_value(game.get_card_miss_counts()).unwrap();
         assert_eq!(*miss_counts_after.get(&card_q).unwrap(), 1);
     }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("hello", "hello"), 0);
+        assert_eq!(levenshtein("hello", "helo"), 1);
+        assert_eq!(levenshtein("hello", "hlelo"), 2);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("crème", "creme"), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_fuzzy_tolerance_accepts_single_typo() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "Hello".to_string(), front: "Q".to_string(), back: "Hello".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        assert!(!game.submit_answer("Helo"));
+        game.set_fuzzy_tolerance(1);
+        assert!(game.submit_answer("Helo"));
+        assert_eq!(game.get_score(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_fuzzy_tolerance_transposition_counts_as_two_edits() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "Hello".to_string(), front: "Q".to_string(), back: "Hello".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        game.set_fuzzy_tolerance(1);
+        assert!(!game.submit_answer("Hlelo"));
+        game.set_fuzzy_tolerance(2);
+        assert!(game.submit_answer("Hlelo"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_fuzzy_tolerance_only_clears_cards_within_tolerance() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q1".to_string(), raw_back: "Hello".to_string(), front: "Q1".to_string(), back: "Hello".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 1, raw_front: "Q2".to_string(), raw_back: "Hallo".to_string(), front: "Q2".to_string(), back: "Hallo".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 2, raw_front: "Q3".to_string(), raw_back: "Goodbye".to_string(), front: "Q3".to_string(), back: "Goodbye".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        game.set_fuzzy_tolerance(1);
+        assert!(game.submit_answer("Hxllo"));
+        let cards: Vec<Card> = serde_wasm_bindgen::from_value(game.get_cards()).unwrap();
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].id, 2);
+        assert_eq!(game.get_score(), 2);
+
+        // Far too many edits away from "Goodbye"
+        assert!(!game.submit_answer("Hello"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_game_config_overrides_max_health() {
+        let config = GameConfig { max_health: Some(10), ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        assert_eq!(game.get_health(), INITIAL_HEALTH);
+        assert_eq!(game.get_max_health(), 10);
+
+        game.cards = (0..40)
+            .map(|i| Card { id: i, raw_front: format!("Q{}", i), raw_back: "A".to_string(), front: format!("Q{}", i), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() })
+            .collect();
+        assert!(game.submit_answer("A"));
+        assert_eq!(game.get_health(), 10);
+
+        game.health = 1;
+        game.restart();
+        assert_eq!(game.get_health(), INITIAL_HEALTH);
+        assert_eq!(game.get_max_health(), 10);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_game_config_overrides_difficulty_curve() {
+        let config = GameConfig {
+            initial_health: Some(1),
+            initial_spawn_interval: Some(2.0),
+            min_spawn_interval: Some(1.5),
+            initial_card_speed: Some(10.0),
+            score_per_spawn_interval_decrease: Some(1),
+            ..GameConfig::default()
+        };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 2.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        assert_eq!(game.get_health(), 1);
+        assert_eq!(game.card_spawn_interval, 2.0);
+        assert_eq!(game.card_speed, 20.0);
+
+        game.cards = (0..5)
+            .map(|i| Card { id: i, raw_front: format!("Q{}", i), raw_back: "A".to_string(), front: format!("Q{}", i), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() })
+            .collect();
+        assert!(game.submit_answer("A"));
+        assert_eq!(game.card_spawn_interval, 1.5);
+        assert_eq!(game.card_speed, (10.0 + 5.0 * CARD_SPEED_INCREASE_PER_SCORE) * 2.0);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_game_state_matches_getters() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.tick(0.5);
+        game.tick(0.5);
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        assert!(game.submit_answer("A"));
+        game.pause();
+
+        let state: GameState = serde_wasm_bindgen::from_value(game.get_game_state()).unwrap();
+        assert_eq!(state.score, game.get_score());
+        assert_eq!(state.score, 1);
+        assert_eq!(state.health, game.get_health());
+        assert_eq!(state.max_health, game.get_max_health());
+        assert_eq!(state.game_over, game.is_game_over());
+        assert_eq!(state.paused, game.is_paused());
+        assert!(state.paused);
+        assert_eq!(state.spawn_interval, game.card_spawn_interval);
+        assert_eq!(state.card_speed, game.card_speed);
+        assert_eq!(state.unlocked_count, 3);
+    }
+
+    #[test]
+    fn test_parse_cards_tab_separated() {
+        let cards = parse_cards(TEST_CARD_DATA);
+        assert_eq!(cards.len(), 3);
+        assert_eq!(cards[2].front, "Iawn");
+        assert_eq!(cards[2].back, "Good / Fine / Okay");
+    }
+
+    #[test]
+    fn test_parse_cards_csv_with_quoted_commas() {
+        let cards = parse_cards("\"Hello, world\",greeting\nfarewell,\"Goodbye, \"\"friend\"\"\"\nno columns here\n");
+        assert_eq!(cards.len(), 2);
+        assert_eq!(cards[0].front, "Hello, world");
+        assert_eq!(cards[0].back, "greeting");
+        assert_eq!(cards[1].front, "farewell");
+        assert_eq!(cards[1].back, "Goodbye, \"friend\"");
+    }
+
+    #[test]
+    fn test_parse_cards_mixed_quoting_and_header() {
+        let text = "#separator:comma\n#html:true\nkey,\"value, with comma\"\n\"quoted\",plain\nplain,plain too\n";
+        let cards = parse_cards(text);
+        assert_eq!(cards.len(), 3);
+        assert_eq!((cards[0].front.as_str(), cards[0].back.as_str()), ("key", "value, with comma"));
+        assert_eq!((cards[1].front.as_str(), cards[1].back.as_str()), ("quoted", "plain"));
+        assert_eq!((cards[2].front.as_str(), cards[2].back.as_str()), ("plain", "plain too"));
+    }
+
+    #[test]
+    fn test_tab_separated_lines_keep_commas() {
+        let cards = parse_cards("Hello, world\tgreeting\n");
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].front, "Hello, world");
+        assert_eq!(cards[0].back, "greeting");
+    }
+
+    #[test]
+    fn test_effective_tolerance_is_clamped_for_short_targets() {
+        assert_eq!(effective_tolerance("a", 3), 0);
+        assert_eq!(effective_tolerance("ab", 3), 0);
+        assert_eq!(effective_tolerance("abc", 3), 1);
+        assert_eq!(effective_tolerance("hello", 3), 2);
+        assert_eq!(effective_tolerance("creme brulee", 1), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_submit_answer_fuzzy() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q1".to_string(), raw_back: "Custard / crème brûlée".to_string(), front: "Q1".to_string(), back: "Custard / crème brûlée".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 1, raw_front: "Q2".to_string(), raw_back: "a".to_string(), front: "Q2".to_string(), back: "a".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        assert!(!game.submit_answer("creme brule"));
+        assert!(!game.submit_answer_fuzzy("wrong", 1));
+        // A single-character target never accepts a different character
+        assert!(!game.submit_answer_fuzzy("b", 1));
+        assert!(game.submit_answer_fuzzy("creme brule", 1));
+
+        let cards: Vec<Card> = serde_wasm_bindgen::from_value(game.get_cards()).unwrap();
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].id, 1);
+        // The one-off tolerance does not change the game's default
+        assert_eq!(game.get_fuzzy_tolerance(), 0);
+    }
+
+    #[test]
+    fn test_parse_cards_optional_hint_and_tags_columns() {
+        let text = "# comment\nOne\tUn\n\nTwo\tDeux\tsounds like 'duh'\nThree\tTrois\t\tnumbers, french ,\n";
+        let cards = parse_cards(text);
+        assert_eq!(cards.len(), 3);
+
+        assert_eq!(cards[0].front, "One");
+        assert_eq!(cards[0].hint, None);
+        assert!(cards[0].tags.is_empty());
+
+        assert_eq!(cards[1].back, "Deux");
+        assert_eq!(cards[1].hint.as_deref(), Some("sounds like 'duh'"));
+        assert!(cards[1].tags.is_empty());
+
+        assert_eq!(cards[2].hint, None);
+        assert_eq!(cards[2].tags, vec!["numbers".to_string(), "french".to_string()]);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_hint_is_carried_to_spawned_card() {
+        let deck_jsvalue = parse_deck("Shwmae\tHello\tA greeting");
+        let game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, deck_jsvalue, JsValue::UNDEFINED).unwrap();
+        let cards: Vec<Card> = serde_wasm_bindgen::from_value(game.get_cards()).unwrap();
+        assert_eq!(cards[0].hint.as_deref(), Some("A greeting"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_skip_card() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q1".to_string(), raw_back: "A".to_string(), front: "Q1".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 1, raw_front: "Q2".to_string(), raw_back: "B".to_string(), front: "Q2".to_string(), back: "B".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        assert_eq!(game.get_skips_remaining(), INITIAL_SKIPS);
+        assert!(game.skip_card(0));
+        assert!(!game.skip_card(0)); // already gone
+
+        let cards: Vec<Card> = serde_wasm_bindgen::from_value(game.get_cards()).unwrap();
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].id, 1);
+        assert_eq!(game.get_skips_remaining(), INITIAL_SKIPS - 1);
+        assert!(!game.card_miss_counts.contains_key("Q1"));
+        assert!(!game.card_success_counts.contains_key("Q1"));
+        assert_eq!(game.get_score(), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_skip_card_out_of_skips() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.skips_remaining = 0;
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        assert!(!game.skip_card(0));
+        let cards: Vec<Card> = serde_wasm_bindgen::from_value(game.get_cards()).unwrap();
+        assert_eq!(cards.len(), 1);
+
+        game.restart();
+        assert_eq!(game.get_skips_remaining(), INITIAL_SKIPS);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_skip_card_while_paused() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        game.pause();
+        assert!(!game.skip_card(0));
+        assert_eq!(game.get_skips_remaining(), INITIAL_SKIPS);
+    }
+
+    #[test]
+    fn test_timed_game_survives_health_loss_until_the_limit() {
+        let config: GameConfig = serde_json::from_str(r#"{"time_limit_seconds": 60.0}"#).unwrap();
+        let mut game = Game::with_deck(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_cards("Q\tA"), config).unwrap();
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards[0].free_misses = 0;
+        let time_to_flip = (800.0 - CARD_HEIGHT) / game.card_speed;
+        game.tick(time_to_flip + 0.1);
+        assert_eq!(game.get_health(), INITIAL_HEALTH - 1);
+        assert!(!game.is_game_over());
+        assert!((game.get_time_remaining().unwrap() - (60.0 - time_to_flip - 0.1)).abs() < 1e-9);
+
+        game.tick(60.0);
+        assert!(game.is_game_over());
+        assert_eq!(game.get_time_remaining(), Some(0.0));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_timed_game_ends_when_timer_drains() {
+        let config = GameConfig { time_limit: Some(2.0), ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        assert_eq!(game.get_time_remaining(), Some(2.0));
+
+        game.tick(0.5);
+        assert_eq!(game.get_time_remaining(), Some(1.5));
+        assert!(!game.is_game_over());
+
+        for _ in 0..3 {
+            game.tick(0.5);
+        }
+        assert_eq!(game.get_time_remaining(), Some(0.0));
+        assert!(game.is_game_over());
+        assert_eq!(game.get_health(), INITIAL_HEALTH);
+
+        game.restart();
+        assert_eq!(game.get_time_remaining(), Some(2.0));
+        assert!(!game.is_game_over());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_untimed_game_has_no_time_remaining() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.tick(100.0);
+        assert_eq!(game.get_time_remaining(), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_combo_multiplier_grows_with_streak() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.card_spawn_interval = 1_000_000.0;
+        let mut expected_score = 0;
+        for streak in 0..8 {
+            assert_eq!(game.get_combo_streak(), streak);
+            let expected_multiplier = match streak {
+                0..=2 => 1,
+                3..=5 => 2,
+                _ => 3,
+            };
+            assert_eq!(game.get_combo_multiplier(), expected_multiplier);
+
+            game.cards = vec![
+                Card { id: 100 + streak, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            ];
+            assert!(game.submit_answer("A"));
+            expected_score += expected_multiplier as i32;
+            assert_eq!(game.get_score(), expected_score);
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn test_combo_multiplier_applies_per_cleared_card() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.combo_streak = 3;
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q1".to_string(), raw_back: "A".to_string(), front: "Q1".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 1, raw_front: "Q2".to_string(), raw_back: "A".to_string(), front: "Q2".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        assert!(game.submit_answer("A"));
+        assert_eq!(game.get_score(), 4);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_missed_card_resets_combo() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.combo_streak = 5;
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
+        ];
+        let time_to_flip = (800.0 - CARD_HEIGHT) / game.card_speed;
+        game.tick(time_to_flip + 0.1);
+        assert_eq!(game.get_combo_streak(), 0);
+        assert_eq!(game.get_combo_multiplier(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_submit_answer_detailed_reports_all_removed_ids() {
+        let mut game = new_game_for_test_large(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.score = 8;
+        game.raw_score = 8;
+        game.cards = vec![
+            Card { id: 10, raw_front: "Q1".to_string(), raw_back: "Answer".to_string(), front: "Q1".to_string(), back: "Answer".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 11, raw_front: "Q2".to_string(), raw_back: "Other".to_string(), front: "Q2".to_string(), back: "Other".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            Card { id: 12, raw_front: "Q3".to_string(), raw_back: "Answer".to_string(), front: "Q3".to_string(), back: "Answer".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        let result: SubmitResult = serde_wasm_bindgen::from_value(game.submit_answer_detailed("answer")).unwrap();
+        assert_eq!(result.removed_ids, vec![10, 12]);
+        assert_eq!(result.score, 10);
+        assert!(result.unlocked);
+
+        let result: SubmitResult = serde_wasm_bindgen::from_value(game.submit_answer_detailed("nope")).unwrap();
+        assert!(result.removed_ids.is_empty());
+        assert_eq!(result.score, 10);
+        assert!(!result.unlocked);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_leitner_box_promotion_reduces_duplicates() {
+        let mut game = new_game_for_test_large(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        let count_in_deck = |game: &Game, front: &str| game.card_deck.iter().filter(|c| c.front == front).count();
+        game.replenish_deck();
+        assert_eq!(count_in_deck(&game, "Card 1"), DECK_CARD_DUPLICATES as usize);
+
+        let mut previous_count = count_in_deck(&game, "Card 1");
+        for i in 0..MAX_LEITNER_BOX + 1 {
+            game.cards = vec![
+                Card { id: 100 + i, raw_front: "Card 1".to_string(), raw_back: "Answer 1".to_string(), front: "Card 1".to_string(), back: "Answer 1".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            ];
+            assert!(game.submit_answer("Answer 1"));
+            game.replenish_deck();
+            let count = count_in_deck(&game, "Card 1");
+            assert!(count <= previous_count);
+            previous_count = count;
+        }
+        let boxes: HashMap<String, u32> = serde_wasm_bindgen::from_value(game.get_card_boxes()).unwrap();
+        assert_eq!(boxes["Card 1"], MAX_LEITNER_BOX);
+        assert_eq!(count_in_deck(&game, "Card 1"), 1);
+        assert_eq!(count_in_deck(&game, "Card 2"), DECK_CARD_DUPLICATES as usize);
+
+        game.restart();
+        let boxes: HashMap<String, u32> = serde_wasm_bindgen::from_value(game.get_card_boxes()).unwrap();
+        assert_eq!(boxes["Card 1"], MAX_LEITNER_BOX);
+    }
+
+    #[test]
+    fn test_every_leitner_box_reduces_duplicates() {
+        let duplicates_by_box = |mut game: Game| -> Vec<u32> {
+            (0..=MAX_LEITNER_BOX)
+                .map(|box_level| {
+                    game.card_boxes.insert("Q".to_string(), box_level);
+                    game.deck_duplicates_for("Q")
+                })
+                .collect()
+        };
+        let deck = DeckConfig { deck_card_duplicates: Some(MAX_LEITNER_BOX + 1), ..DeckConfig::default() };
+        let config = GameConfig { deck, ..GameConfig::default() };
+        let game = Game::with_deck(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_cards(TEST_CARD_DATA), config).unwrap();
+        assert_eq!(duplicates_by_box(game), (1..=MAX_LEITNER_BOX + 1).rev().collect::<Vec<_>>());
+
+        // With the default count, boxes still never gain copies and the ends are fixed
+        let game = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA).unwrap();
+        let duplicates = duplicates_by_box(game);
+        assert!(duplicates.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert_eq!(duplicates[0], DECK_CARD_DUPLICATES);
+        assert!(duplicates[1] < DECK_CARD_DUPLICATES);
+        assert_eq!(duplicates[MAX_LEITNER_BOX as usize], 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_leitner_box_resets_on_miss() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.card_boxes.insert("Q".to_string(), 3);
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
+        ];
+        let time_to_flip = (800.0 - CARD_HEIGHT) / game.card_speed;
+        game.tick(time_to_flip + 0.1);
+        assert_eq!(game.card_boxes["Q"], 0);
+        // Back to the full count, plus a copy for the miss without a success
+        assert_eq!(game.deck_duplicates_for("Q"), DECK_CARD_DUPLICATES + 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_deck_config_controls_unlock_schedule() {
+        let config = GameConfig {
+            deck: DeckConfig {
+                initial_unlocked_cards: Some(4),
+                score_per_card_unlock: Some(3),
+                cards_per_unlock: Some(2),
+                deck_card_duplicates: Some(2),
+                ..DeckConfig::default()
+            },
+            ..GameConfig::default()
+        };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA_LARGE), config_jsvalue).unwrap();
+        assert_eq!(game.unlocked_cards_count, 4);
+        assert_eq!(game.card_deck.len(), 4 * 2 - 1);
+
+        game.score = 2;
+        game.raw_score = 2;
+        game.cards = vec![
+            Card { id: 100, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        assert!(game.submit_answer("A"));
+        assert_eq!(game.get_score(), 3);
+        assert_eq!(game.unlocked_cards_count, 6);
+        assert_eq!(game.card_deck.len(), 6 * 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_hardcore_mode_ends_game_on_first_miss() {
+        let config = GameConfig { hardcore: true, ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        assert!(game.is_hardcore());
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
+        ];
+        let time_to_flip = (800.0 - CARD_HEIGHT) / game.card_speed;
+        game.tick(time_to_flip + 0.1);
+        assert!(game.is_game_over());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_normal_mode_tolerates_misses() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        assert!(!game.is_hardcore());
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q1".to_string(), raw_back: "A".to_string(), front: "Q1".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
+            Card { id: 1, raw_front: "Q2".to_string(), raw_back: "A".to_string(), front: "Q2".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        let time_to_flip = (800.0 - CARD_HEIGHT) / game.card_speed;
+        game.tick(time_to_flip + 0.1);
+        assert!(!game.is_game_over());
+        assert_eq!(game.get_health(), INITIAL_HEALTH - 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_save_state_round_trip() {
+        let mut game = new_game_for_test_large(600.0, 800.0, 7, GameMode::Both, 1.0);
+        for _ in 0..20 {
+            game.tick(0.7);
+        }
+        let cards: Vec<Card> = serde_wasm_bindgen::from_value(game.get_cards()).unwrap();
+        if let Some(card) = cards.iter().find(|c| !c.flipped) {
+            let answer = card.back.clone();
+            game.submit_answer(&answer);
+        }
+
+        let saved = game.to_save_state();
+        let mut loaded = Game::from_save_state(&saved).unwrap();
+        assert_eq!(loaded.to_save_state(), saved);
+
+        for _ in 0..30 {
+            game.tick(0.4);
+            loaded.tick(0.4);
+        }
+        let original_cards: Vec<Card> = serde_wasm_bindgen::from_value(game.get_cards()).unwrap();
+        let loaded_cards: Vec<Card> = serde_wasm_bindgen::from_value(loaded.get_cards()).unwrap();
+        assert_eq!(original_cards.len(), loaded_cards.len());
+        for (original, loaded) in original_cards.iter().zip(&loaded_cards) {
+            assert_eq!(original.id, loaded.id);
+            assert_eq!(original.front, loaded.front);
+            assert_eq!(original.x, loaded.x);
+            assert_eq!(original.y, loaded.y);
+        }
+        assert_eq!(game.get_score(), loaded.get_score());
+        assert_eq!(game.get_health(), loaded.get_health());
+    }
+
+    #[test]
+    fn test_load_save_state_without_newer_fields() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 7, GameMode::Normal, 1.0, TEST_CARD_DATA).unwrap();
+        game.tick(0.5);
+        let mut state: serde_json::Value = serde_json::from_str(&game.to_save_state()).unwrap();
+        let saved_game = state["game"].as_object_mut().unwrap();
+        for field in ["card_schedules", "replay_log", "excluded", "hints_used", "slowmo_remaining", "queued_answers"] {
+            assert!(saved_game.remove(field).is_some());
+        }
+        saved_game["cards"][0].as_object_mut().unwrap().remove("age");
+
+        let loaded = Game::from_save_state(&state.to_string()).unwrap();
+        assert_eq!(loaded.cards.len(), game.cards.len());
+        assert_eq!(loaded.cards[0].age, 0.0);
+        assert!(loaded.card_schedules.is_empty());
+        assert_eq!(loaded.get_score(), game.get_score());
+    }
+
+    #[test]
+    fn test_rng_word_pos_restores_the_stream() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 3, GameMode::Both, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        for _ in 0..5 {
+            game.spawn_card();
+        }
+        let mut other = Game::from_deck_text(600.0, 800.0, 3, GameMode::Both, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        assert_ne!(other.rng_word_pos(), game.rng_word_pos());
+        other.set_rng_word_pos(game.rng_word_pos());
+        assert_eq!(other.rng_word_pos(), game.rng_word_pos());
+        assert_eq!(other.rng.random::<u64>(), game.rng.random::<u64>());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_load_rejects_unknown_version() {
+        let game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        let saved = game.to_save_state().replacen(&format!("\"version\":{}", SAVE_STATE_VERSION), "\"version\":999", 1);
+        assert!(Game::from_save_state(&saved).is_err());
+        assert!(Game::from_save_state("not json").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_rng_stream_survives_reload() {
+        let mut game = new_game_for_test_large(600.0, 800.0, 3, GameMode::Both, 1.0);
+        let initial_pos = game.rng_word_pos();
+        for _ in 0..5 {
+            game.spawn_card();
+        }
+        assert!(game.rng_word_pos() > initial_pos);
+
+        let mut loaded = Game::from_save_state(&game.to_save_state()).unwrap();
+        assert_eq!(loaded.rng_word_pos(), game.rng_word_pos());
+
+        for _ in 0..10 {
+            game.spawn_card();
+            loaded.spawn_card();
+        }
+        let original_cards: Vec<Card> = serde_wasm_bindgen::from_value(game.get_cards()).unwrap();
+        let loaded_cards: Vec<Card> = serde_wasm_bindgen::from_value(loaded.get_cards()).unwrap();
+        for (original, loaded) in original_cards.iter().zip(&loaded_cards).skip(6) {
+            assert_eq!(original.front, loaded.front);
+            assert_eq!(original.back, loaded.back);
+            assert_eq!(original.x, loaded.x);
+        }
+        assert_eq!(loaded.rng_word_pos(), game.rng_word_pos());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_missed_card_emits_events() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        let spawned: Vec<GameEvent> = serde_wasm_bindgen::from_value(game.drain_events()).unwrap();
+        assert_eq!(spawned, vec![GameEvent::CardSpawned { id: 0 }]);
+
+        game.health = 1;
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards = vec![
+            Card { id: 7, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        let time_to_flip = (800.0 - CARD_HEIGHT) / game.card_speed;
+        game.tick(time_to_flip + 0.1);
+
+        let events: Vec<GameEvent> = serde_wasm_bindgen::from_value(game.drain_events()).unwrap();
+        assert_eq!(events, vec![GameEvent::CardMissed { id: 7 }, GameEvent::HeartLost, GameEvent::GameOver]);
+        let drained: Vec<GameEvent> = serde_wasm_bindgen::from_value(game.drain_events()).unwrap();
+        assert!(drained.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_clearing_five_cards_emits_heart_gained() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.drain_events();
+        game.cards = (0..5)
+            .map(|i| Card { id: i, raw_front: format!("Q{}", i), raw_back: "A".to_string(), front: format!("Q{}", i), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() })
+            .collect();
+        assert!(game.submit_answer("A"));
+
+        let events: Vec<GameEvent> = serde_wasm_bindgen::from_value(game.drain_events()).unwrap();
+        let cleared = events.iter().filter(|e| matches!(e, GameEvent::CardCleared { .. })).count();
+        assert_eq!(cleared, 5);
+        assert!(events.contains(&GameEvent::HeartGained));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_practice_mode_never_loses_health() {
+        let config = GameConfig { practice: true, ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        assert!(game.is_practice());
+        game.health = 1;
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        let time_to_flip = (800.0 - CARD_HEIGHT) / game.card_speed;
+        game.tick(time_to_flip + 0.1);
+
+        let cards: Vec<Card> = serde_wasm_bindgen::from_value(game.get_cards()).unwrap();
+        assert!(cards[0].flipped);
+        assert_eq!(game.get_health(), 1);
+        assert!(!game.is_game_over());
+        assert_eq!(game.card_miss_counts["Q"], 1);
+
+        game.restart();
+        assert!(game.is_practice());
+    }
+
+    #[test]
+    fn test_card_schedule_intervals() {
+        let mut schedule = CardSchedule::default();
+        schedule.record_success(1);
+        assert_eq!((schedule.interval, schedule.due_round), (1, 2));
+        schedule.record_success(2);
+        assert_eq!((schedule.interval, schedule.due_round), (2, 4));
+        schedule.record_success(4);
+        assert_eq!(schedule.interval, 5);
+        schedule.record_miss(5);
+        assert_eq!((schedule.interval, schedule.due_round), (0, 5));
+        assert!(schedule.ease < INITIAL_SCHEDULE_EASE + 3.0 * SCHEDULE_EASE_BONUS);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_missed_card_is_scheduled_ahead_of_mastered_card() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.card_spawn_interval = 1_000_000.0;
+
+        // "Shwmae" is answered correctly several times
+        for i in 0..3 {
+            game.cards = vec![
+                Card { id: 100 + i, raw_front: "Shwmae".to_string(), raw_back: "Hello".to_string(), front: "Shwmae".to_string(), back: "Hello".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+            ];
+            assert!(game.submit_answer("Hello"));
+            game.replenish_deck();
+        }
+        // "Iawn" is missed
+        game.cards = vec![
+            Card { id: 200, raw_front: "Iawn".to_string(), raw_back: "Good".to_string(), front: "Iawn".to_string(), back: "Good".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
+        ];
+        let time_to_flip = (800.0 - CARD_HEIGHT) / game.card_speed;
+        game.tick(time_to_flip + 0.1);
+        game.replenish_deck();
+
+        let schedules: HashMap<String, CardSchedule> = serde_wasm_bindgen::from_value(game.get_card_schedules()).unwrap();
+        assert!(schedules["Shwmae"].due_round > game.deck_round);
+        assert_eq!(schedules["Iawn"].interval, 0);
+
+        let count_in_deck = |front: &str| game.card_deck.iter().filter(|c| c.front == front).count();
+        assert!(count_in_deck("Iawn") > count_in_deck("Shwmae"));
+        // Due cards are drawn before cards that are not due yet
+        let first_mastered = game.card_deck.iter().rposition(|c| c.front == "Shwmae").unwrap();
+        let last_missed = game.card_deck.iter().position(|c| c.front == "Iawn").unwrap();
+        assert!(first_mastered < last_missed);
+    }
+
+    #[test]
+    fn test_escape_anki_field() {
+        assert_eq!(escape_anki_field("line one\nline two"), "line one<br>line two");
+        assert_eq!(escape_anki_field("a\r\nb"), "a<br>b");
+        assert_eq!(escape_anki_field("a\tb"), "a&#9;b");
+        assert_eq!(escape_anki_field("salt & pepper"), "salt &amp; pepper");
+        assert_eq!(escape_anki_field("caf&eacute; &amp; &#233;"), "caf&eacute; &amp; &#233;");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_anki_export_keeps_one_line_per_card() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.missed_cards = vec![
+            Card { id: 1, raw_front: "Bore da".to_string(), raw_back: "Good\nmorning".to_string(), front: "Bore da".to_string(), back: "Good\nmorning".to_string(), ..Default::default() },
+            Card { id: 2, raw_front: "Bara\tmenyn".to_string(), raw_back: "Bread & butter".to_string(), front: "Bara menyn".to_string(), back: "Bread & butter".to_string(), ..Default::default() },
+        ];
+
+        let export = game.generate_anki_export();
+        let lines: Vec<&str> = export.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(lines, vec!["Bore da\tGood<br>morning", "Bara&#9;menyn\tBread &amp; butter"]);
+        assert!(lines.iter().all(|l| l.matches('\t').count() == 1));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_unicode_normalization_matches_cjk_answer() {
+        let config = GameConfig { normalization: TextNormalization::Unicode, ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        game.cards = vec![
+            Card { id: 0, raw_front: "Tokyo".to_string(), raw_back: "東京".to_string(), front: "Tokyo".to_string(), back: "東京".to_string(), ..Default::default() },
+        ];
+        assert!(!game.submit_answer("京都"));
+        assert!(game.submit_answer("東京"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_accuracy_is_zero_without_answers() {
+        let game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        assert_eq!(game.get_total_answered(), 0);
+        assert_eq!(game.get_accuracy(), 0.0);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_accuracy_from_successes_and_misses() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.card_success_counts.insert("Q1".to_string(), 2);
+        game.card_success_counts.insert("Q2".to_string(), 1);
+        game.card_miss_counts.insert("Q1".to_string(), 1);
+        assert_eq!(game.get_total_answered(), 4);
+        assert_eq!(game.get_accuracy(), 0.75);
+
+        game.restart();
+        assert_eq!(game.get_total_answered(), 4);
+        assert_eq!(game.get_accuracy(), 0.75);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_statistics_track_best_streak() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.card_spawn_interval = 1_000_000.0;
+        for i in 0..3 {
+            game.cards = vec![
+                Card { id: i, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
+            ];
+            assert!(game.submit_answer("A"));
+        }
+        game.cards = vec![
+            Card { id: 10, raw_front: "Q2".to_string(), raw_back: "A2".to_string(), front: "Q2".to_string(), back: "A2".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
+        ];
+        let time_to_flip = (800.0 - CARD_HEIGHT) / game.card_speed;
+        game.tick(time_to_flip + 0.1);
+
+        let stats: Statistics = serde_wasm_bindgen::from_value(game.get_statistics()).unwrap();
+        assert_eq!(stats.total_answers, 4);
+        assert_eq!(stats.total_misses, 1);
+        assert_eq!(stats.accuracy_percent, 75.0);
+        assert_eq!(stats.current_streak, 0);
+        assert_eq!(stats.best_streak, 3);
+
+        game.restart();
+        let stats: Statistics = serde_wasm_bindgen::from_value(game.get_statistics()).unwrap();
+        assert_eq!(stats.total_answers, 4);
+        assert_eq!(stats.best_streak, 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_free_misses_for_new_cards_is_configurable() {
+        let config = GameConfig { free_misses_for_new_cards: Some(0), ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        assert_eq!(game.cards[0].free_misses, 0);
+
+        game.restart();
+        assert_eq!(game.cards[0].free_misses, 0);
+
+        let mut default_game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        assert_eq!(default_game.cards[0].free_misses, 2);
+        default_game.restart();
+        assert_eq!(default_game.cards[0].free_misses, 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_serialize_state_round_trip() {
+        let mut game = new_game_for_test_large(600.0, 800.0, 7, GameMode::Both, 1.0);
+        for _ in 0..10 {
+            game.tick(0.9);
+        }
+        let restored = Game::from_state(&game.serialize_state()).unwrap();
+
+        let card_ids = |g: &Game| g.cards.iter().map(|c| (c.id, c.front.clone(), c.y)).collect::<Vec<_>>();
+        let deck_fronts = |g: &Game| g.card_deck.iter().map(|c| c.front.clone()).collect::<Vec<_>>();
+        assert_eq!(card_ids(&restored), card_ids(&game));
+        assert_eq!(deck_fronts(&restored), deck_fronts(&game));
+        assert_eq!(restored.get_score(), game.get_score());
+        assert_eq!(restored.rng_word_pos(), game.rng_word_pos());
+    }
+
+    #[test]
+    fn test_punctuation_is_ignored_on_both_sides() {
+        let matches = |alternative: &str, answer: &str| {
+            let options = NormalizeOptions::default();
+            answer_matches(alternative, &normalize_with(answer, options), 0, options)
+        };
+        assert!(matches("hello", "hello."));
+        assert!(matches("hello.", "hello"));
+        assert!(matches("dont", "don't"));
+        assert!(matches("don't", "dont"));
+        assert!(matches("don't", "don't"));
+        assert!(matches("to be, or not to be", "to be or not to be."));
+        assert!(!matches("hello", "hallo."));
+    }
+
+    #[test]
+    fn test_normalize_case_sensitive_keeps_case() {
+        let options = NormalizeOptions { case_sensitive: true, ..NormalizeOptions::default() };
+        assert_eq!(normalize_with("  Das Haus!  ", options), "Das Haus");
+        assert_eq!(normalize_with("Crème", options), "Creme");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_case_sensitive_answers() {
+        let haus_card = || vec![
+            Card { id: 0, raw_front: "house".to_string(), raw_back: "Haus".to_string(), front: "house".to_string(), back: "Haus".to_string(), ..Default::default() },
+        ];
+
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.cards = haus_card();
+        assert!(game.submit_answer("haus"));
+
+        let config = GameConfig { case_sensitive: true, ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        game.cards = haus_card();
+        assert!(!game.submit_answer("haus"));
+        assert!(game.submit_answer("Haus"));
+    }
+
+    #[test]
+    fn test_case_sensitive_mode_requires_exact_case() {
+        let apfel_card = || vec![Card { raw_front: "apple".to_string(), back: "Apfel".to_string(), ..Default::default() }];
+        let new_game = |case_sensitive| {
+            let config = GameConfig { case_sensitive, ..GameConfig::default() };
+            let mut game = Game::with_deck(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_cards("apple\tApfel"), config).unwrap();
+            game.cards = apfel_card();
+            game
+        };
+
+        assert!(new_game(false).submit_answer("apfel"));
+        assert!(new_game(false).submit_answer("  APFEL "));
+        assert!(!new_game(true).submit_answer("apfel"));
+        assert!(new_game(true).submit_answer("  Apfel "));
+    }
+
+    #[test]
+    fn test_accents_are_required_without_diacritic_stripping() {
+        let new_game = |strip_diacritics| {
+            let config = GameConfig { strip_diacritics, ..GameConfig::default() };
+            let mut game = Game::with_deck(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_cards("there\tallá"), config).unwrap();
+            game.cards = vec![Card { raw_front: "there".to_string(), back: "allá".to_string(), ..Default::default() }];
+            game
+        };
+
+        assert!(new_game(None).submit_answer("alla"));
+        assert!(!new_game(Some(false)).submit_answer("alla"));
+        assert!(new_game(Some(false)).submit_answer("Allá!"));
+        assert!(new_game(Some(false)).submit_answer("alla\u{301}"));
+    }
+
+    #[test]
+    fn test_required_answers_clear_only_once_all_are_submitted() {
+        let config = GameConfig { require_all_answers: true, ..GameConfig::default() };
+        let mut game = Game::with_deck(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_cards("colours of the flag\tred / green"), config).unwrap();
+        game.cards = vec![Card { id: 7, raw_front: "colours of the flag".to_string(), back: "red / green".to_string(), ..Default::default() }];
+
+        assert!(!game.submit_answer("red"));
+        assert!(!game.submit_answer("red"));
+        assert_eq!(game.cards.len(), 1);
+        assert_eq!(game.cards[0].answered_alternatives, vec![0]);
+        assert!(game.submit_answer("green"));
+        assert!(game.cards.iter().all(|card| card.id != 7));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_flip_display_seconds() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        assert_eq!(game.get_flip_display_seconds(), 1.0);
+        game.set_flip_display_seconds(0.0);
+        assert_eq!(game.get_flip_display_seconds(), MIN_FLIP_DISPLAY_SECONDS);
+        game.set_flip_display_seconds(2.0);
+
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: true, time_since_flipped: Some(0.0), free_misses: 2, ..Default::default() },
+        ];
+        game.tick(1.5);
+        assert_eq!(game.cards.len(), 1);
+        game.tick(1.0);
+        assert!(game.cards.is_empty());
+    }
+
+    #[test]
+    fn test_find_duplicate_fronts() {
+        let text = "#separator:tab\nBore da\tGood morning\nNos da\tGood night\n\nBore da\tMorning\n";
+        assert_eq!(find_duplicate_fronts(text), vec![
+            DuplicateFront { front: "Bore da".to_string(), lines: vec![2, 5] },
+        ]);
+        assert_eq!(parse_cards(text).len(), 3);
+    }
+
+    #[test]
+    fn test_find_duplicate_fronts_clean_deck() {
+        assert!(find_duplicate_fronts(TEST_CARD_DATA).is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_validate_deck_reports_duplicates() {
+        let report: DeckReport = serde_wasm_bindgen::from_value(validate_deck("Q\tA\nQ\tB")).unwrap();
+        assert_eq!(report.duplicates, vec![DuplicateFront { front: "Q".to_string(), lines: vec![1, 2] }]);
+        let report: DeckReport = serde_wasm_bindgen::from_value(validate_deck(TEST_CARD_DATA)).unwrap();
+        assert!(report.duplicates.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_missed_review_deck_contains_unique_missed_pairs() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Reverse, 1.0);
+        let missed = |id: u32, raw_front: &str, raw_back: &str| Card {
+            id,
+            raw_front: raw_front.to_string(),
+            raw_back: raw_back.to_string(),
+            front: raw_back.to_string(),
+            back: raw_front.to_string(),
+            flipped: true,
+            ..Default::default()
+        };
+        game.missed_cards = vec![missed(1, "Bore da", "Good morning"), missed(2, "Nos da", "Good night"), missed(3, "Bore da", "Good morning")];
+
+        let deck: Vec<CustomCard> = serde_wasm_bindgen::from_value(game.to_missed_review_deck()).unwrap();
+        let pairs: Vec<(&str, &str)> = deck.iter().map(|c| (c.front.as_str(), c.back.as_str())).collect();
+        assert_eq!(pairs, vec![("Bore da", "Good morning"), ("Nos da", "Good night")]);
+
+        let review = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, game.to_missed_review_deck(), JsValue::UNDEFINED).unwrap();
+        assert_eq!(review.card_data.len(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_max_cards_cap_limits_cards_on_screen() {
+        let config = GameConfig { max_cards_cap: Some(3), ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 100_000.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        game.score = 1000;
+        game.card_spawn_interval = 0.1;
+        for _ in 0..50 {
+            game.tick(0.2);
+            assert!(game.cards.len() <= 3);
+        }
+        assert_eq!(game.cards.len(), 3);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_miss_weight_favors_missed_cards() {
+        let config = GameConfig { deck: DeckConfig { miss_weight: Some(2.0), ..DeckConfig::default() }, ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 3, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA_LARGE), config_jsvalue).unwrap();
+        game.card_miss_counts.insert("Card 1".to_string(), 3);
+        assert_eq!(game.miss_extra_duplicates("Card 1"), MAX_MISS_EXTRA_DUPLICATES);
+        assert_eq!(game.miss_extra_duplicates("Card 2"), 0);
+
+        game.card_deck.clear();
+        let mut spawned: HashMap<String, usize> = HashMap::new();
+        for _ in 0..200 {
+            game.cards.clear();
+            game.spawn_card();
+            *spawned.entry(game.cards[0].raw_front.clone()).or_insert(0) += 1;
+        }
+        let others_max = spawned.iter().filter(|(front, _)| *front != "Card 1").map(|(_, n)| *n).max().unwrap();
+        assert!(spawned["Card 1"] > 2 * others_max);
+    }
+
+    #[test]
+    fn test_reshuffle_deck_is_a_deterministic_permutation() {
+        let new_game = || Game::from_deck_text(600.0, 800.0, 5, GameMode::Normal, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        let fronts = |game: &Game| game.card_deck.iter().map(|card| card.front.clone()).collect::<Vec<_>>();
+        let mut game = new_game();
+        let before = fronts(&game);
+        game.reshuffle_deck();
+        let after = fronts(&game);
+        assert_ne!(before, after);
+
+        let mut sorted_before = before.clone();
+        let mut sorted_after = after.clone();
+        sorted_before.sort();
+        sorted_after.sort();
+        assert_eq!(sorted_before, sorted_after);
+
+        let mut other = new_game();
+        other.reshuffle_deck();
+        assert_eq!(fronts(&other), after);
+    }
+
+    #[test]
+    fn test_reseed_changes_later_draws_only() {
+        let new_game = || Game::from_deck_text(600.0, 800.0, 11, GameMode::Normal, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        let fronts = |game: &Game| game.card_deck.iter().map(|card| card.front.clone()).collect::<Vec<_>>();
+        let mut first = new_game();
+        let mut second = new_game();
+        assert_eq!(first.get_rng_seed(), 11);
+        assert_eq!(first.cards[0].raw_front, second.cards[0].raw_front);
+        assert_eq!(first.cards[0].x, second.cards[0].x);
+
+        second.score = 4;
+        second.reseed(12);
+        assert_eq!(second.get_rng_seed(), 12);
+        assert_eq!(second.score, 4);
+        assert_eq!(fronts(&first), fronts(&second));
+        first.reshuffle_deck();
+        second.reshuffle_deck();
+        assert_ne!(fronts(&first), fronts(&second));
+
+        let mut third = new_game();
+        third.reseed(12);
+        third.reshuffle_deck();
+        assert_eq!(fronts(&third), fronts(&second));
+    }
+
+    #[test]
+    fn test_missed_cards_are_aggregated_by_front() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA).unwrap();
+        let missed = |front: &str| Card { raw_front: front.to_string(), front: front.to_string(), ..Default::default() };
+        game.missed_cards = vec![missed("Iawn"), missed("Shwmae"), missed("Shwmae")];
+
+        let summaries = game.missed_card_summaries();
+        let counts: Vec<(&str, u32)> = summaries.iter().map(|s| (s.card.raw_front.as_str(), s.times_missed)).collect();
+        assert_eq!(counts, vec![("Shwmae", 2), ("Iawn", 1)]);
+        assert_eq!(game.missed_cards.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_cards_category_column() {
+        let cards = parse_cards("Hund\tdog\t\t\tvocabulary\nder\tthe\nHaus\thouse\n");
+        assert_eq!(cards[0].category.as_deref(), Some("vocabulary"));
+        assert_eq!(cards[1].category, None);
+
+        let cards = parse_cards("#category column:3\nHund\tdog\tvocabulary\n");
+        assert_eq!(cards[0].category.as_deref(), Some("vocabulary"));
+        assert_eq!(cards[0].hint, None);
+    }
+
+    #[test]
+    fn test_categories_score_independently() {
+        let deck = "Hund\tdog\t\t\tvocabulary\nKatze\tcat\t\t\tvocabulary\nder\tthe (masculine)\t\t\tgrammar\nja\tyes";
+        let mut game = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, deck).unwrap();
+        let card = |front: &str, back: &str, category: Option<&str>| Card {
+            raw_front: front.to_string(),
+            back: back.to_string(),
+            category: category.map(|c| c.to_string()),
+            ..Default::default()
+        };
+        game.cards = vec![card("Hund", "dog", Some("vocabulary")), card("Katze", "cat", Some("vocabulary")), card("der", "the", Some("grammar")), card("ja", "yes", None)];
+        for answer in ["dog", "cat", "the", "yes"] {
+            // Without a combo, every card is worth one point
+            game.combo_streak = 0;
+            assert!(game.submit_answer(answer));
+        }
+        assert_eq!(game.category_scores["vocabulary"], 2);
+        assert_eq!(game.category_scores["grammar"], 1);
+        assert_eq!(game.category_scores[DEFAULT_CATEGORY], 1);
+        assert_eq!(game.category_scores.values().sum::<i32>(), game.score);
+    }
+
+    #[test]
+    fn test_card_points_from_deck() {
+        let deck = "#points column:3\nHund\tdog\t3\nja\tyes\n";
+        let cards = parse_cards(deck);
+        assert_eq!(cards[0].points, Some(3));
+        assert_eq!(cards[0].hint, None);
+        assert_eq!(cards[1].points, None);
+
+        let mut game = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, deck).unwrap();
+        let card = |front: &str, back: &str, points: Option<u32>| Card { raw_front: front.to_string(), back: back.to_string(), points, ..Default::default() };
+        game.cards = vec![card("Hund", "dog", Some(3)), card("ja", "yes", None)];
+        game.combo_streak = 0;
+        assert!(game.submit_answer("dog"));
+        assert_eq!(game.score, 3);
+        game.combo_streak = 0;
+        assert!(game.submit_answer("yes"));
+        assert_eq!(game.score, 4);
+    }
+
+    #[test]
+    fn test_new_cards_preview_their_answer() {
+        let config = GameConfig { answer_preview_seconds: 1.0, ..GameConfig::default() };
+        let mut game = Game::with_deck(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_cards(TEST_CARD_DATA), config).unwrap();
+        game.card_spawn_interval = 1_000_000.0;
+        assert!(RenderableCard::from(&game.cards[0]).show_answer);
+
+        game.tick(0.5);
+        assert!(RenderableCard::from(&game.cards[0]).show_answer);
+        game.tick(0.6);
+        assert!(!RenderableCard::from(&game.cards[0]).show_answer);
+
+        // Cards that have been seen before spawn without a preview
+        let front = game.cards[0].raw_front.clone();
+        game.card_success_counts.insert(front.clone(), 1);
+        game.cards.clear();
+        game.card_deck.clear();
+        game.card_deck.push(game.card_data.iter().find(|c| c.front == front).unwrap().clone());
+        game.spawn_card();
+        assert!(!RenderableCard::from(&game.cards[0]).show_answer);
+    }
+
+    #[test]
+    fn test_missed_cards_summary_orders_by_total_misses() {
+        let mut game = Game::from_deck_text(600.0, 100_000.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA).unwrap();
+        game.card_spawn_interval = 1_000_000.0;
+        let card = |front: &str| Card { raw_front: front.to_string(), front: front.to_string(), back: "x".to_string(), y: 100_000.0, ..Default::default() };
+        for front in ["Iawn", "Shwmae", "Shwmae"] {
+            game.cards = vec![card(front)];
+            game.tick(0.01);
+        }
+        game.card_success_counts.insert("Iawn".to_string(), 4);
+
+        let summary = game.missed_card_counts();
+        let counts: Vec<(&str, u32, u32)> = summary.iter().map(|s| (s.card.raw_front.as_str(), s.miss_count, s.success_count)).collect();
+        assert_eq!(counts, vec![("Shwmae", 2, 0), ("Iawn", 1, 4)]);
+    }
+
+    #[test]
+    fn test_choices_contain_the_answer_and_other_backs() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        let deck_backs: Vec<String> = game.card_data.iter().map(|card| card.back.clone()).collect();
+        for _ in 0..10 {
+            let (id, back) = (game.cards[0].id, game.cards[0].back.clone());
+            let choices = game.choices_for_card(id, 4);
+            assert_eq!(choices.len(), 4);
+            assert_eq!(choices.iter().filter(|choice| **choice == back).count(), 1);
+            assert!(choices.iter().all(|choice| deck_backs.contains(choice)));
+            let distinct: HashSet<&String> = choices.iter().collect();
+            assert_eq!(distinct.len(), 4);
+
+            let wrong = choices.iter().find(|choice| **choice != back).unwrap().clone();
+            assert!(!game.submit_choice(id, &wrong));
+            assert!(game.submit_choice(id, &back));
+            game.spawn_card();
+        }
+
+        let mut other = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        let mut again = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        assert_eq!(other.choices_for_card(0, 3), again.choices_for_card(0, 3));
+        assert!(other.choices_for_card(999, 3).is_empty());
+    }
+
+    #[test]
+    fn test_long_combo_grants_slowmo() {
+        let mut game = Game::from_deck_text(600.0, 100_000.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA).unwrap();
+        game.card_spawn_interval = 1_000_000.0;
+        for _ in 0..SLOWMO_COMBO_THRESHOLD {
+            assert!(!game.is_slow_mo_active());
+            game.cards.push(Card { id: 1000, back: "x".to_string(), ..Default::default() });
+            assert!(game.submit_answer("x"));
+        }
+        assert!(game.is_slow_mo_active());
+        assert_eq!(game.get_slowmo_remaining(), SLOWMO_COMBO_SECONDS);
+
+        game.cards = vec![Card { id: 1001, ..Default::default() }];
+        let speed = game.card_speed;
+        game.tick(1.0);
+        assert_eq!(game.cards[0].y, speed * SLOWMO_SPEED_FACTOR);
+        game.tick(SLOWMO_COMBO_SECONDS);
+        assert!(!game.is_slow_mo_active());
+        let y = game.cards[0].y;
+        game.tick(1.0);
+        assert_eq!(game.cards[0].y - y, speed);
+    }
+
+    #[test]
+    fn test_queued_answers_resolve_on_the_next_tick() {
+        let mut game = Game::from_deck_text(600.0, 100_000.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA).unwrap();
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards = vec![
+            Card { id: 100, back: "one".to_string(), ..Default::default() },
+            Card { id: 101, back: "two".to_string(), ..Default::default() },
+        ];
+        game.queue_answer("two");
+        game.queue_answer("one");
+        assert_eq!(game.cards.len(), 2);
+        game.tick(0.1);
+        assert!(game.cards.is_empty());
+        assert_eq!(game.score, 2);
+
+        game.queue_answer("three");
+        game.pause();
+        game.resume();
+        assert!(game.queued_answers.is_empty());
+    }
+
+    #[test]
+    fn test_set_speed_multiplier_keeps_the_score_component() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA).unwrap();
+        game.score = 10;
+        game.set_speed_multiplier(2.0);
+        let expected = (INITIAL_CARD_SPEED + 10.0 * CARD_SPEED_INCREASE_PER_SCORE) * 2.0;
+        assert_eq!(game.card_speed, expected);
+
+        game.set_speed_multiplier(0.5);
+        assert_eq!(game.card_speed, expected / 4.0);
+
+        game.set_speed_multiplier(-1.0);
+        assert_eq!(game.card_speed, (INITIAL_CARD_SPEED + 10.0 * CARD_SPEED_INCREASE_PER_SCORE) * MIN_SPEED_MULTIPLIER);
+    }
+
+    #[test]
+    fn test_replay_applies_speed_changes_when_they_happened() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 1, GameMode::Normal, 1.0, TEST_CARD_DATA).unwrap();
+        game.tick(1.0);
+        game.set_speed_multiplier(3.0);
+        game.tick(1.0);
+
+        let replay: Replay = serde_json::from_str(&game.export_replay()).unwrap();
+        assert_eq!(replay.speed_multiplier, 1.0);
+        let replayed = Game::replay_with_deck(1, GameMode::Normal, parse_cards(TEST_CARD_DATA), &game.export_replay()).unwrap();
+        assert_eq!(replayed.speed_multiplier, 3.0);
+        assert_eq!(replayed.cards[0].y, game.cards[0].y);
+    }
+
+    #[test]
+    fn test_unlock_progress() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        game.raw_score = 3;
+        let expected = UnlockProgress {
+            unlocked: INITIAL_UNLOCKED_CARDS,
+            total: 15,
+            score_to_next_unlock: Some(SCORE_PER_CARD_UNLOCK - 3),
+        };
+        assert_eq!(game.unlock_progress(), expected);
+
+        game.raw_score = 1000;
+        let progress = game.unlock_progress();
+        assert_eq!(progress.unlocked, progress.total);
+        assert_eq!(progress.score_to_next_unlock, None);
+    }
+
+    #[test]
+    fn test_loaded_stats_reduce_deck_duplicates() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA).unwrap();
+        let fresh_deck_len = game.card_deck.len() + game.cards.len();
+
+        let success_counts: HashMap<String, u32> = game.card_data.iter().map(|card| (card.front.clone(), 10)).collect();
+        let miss_counts = HashMap::from([("Shwmae".to_string(), 1)]);
+        game.apply_stats(success_counts, miss_counts);
+        assert!(game.card_deck.len() < fresh_deck_len);
+        assert_eq!(game.card_deck.len(), game.card_data.len());
+        assert_eq!(game.cards[0].free_misses, 0);
+        assert_eq!(game.card_success_counts["Shwmae"], 10);
+        assert_eq!(game.card_miss_counts["Shwmae"], 1);
+    }
+
+    #[test]
+    fn test_replenished_deck_repeats_missed_cards_more_often() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        game.card_miss_counts.insert("Card 1".to_string(), 4);
+        game.card_miss_counts.insert("Card 3".to_string(), 4);
+        game.card_success_counts.insert("Card 3".to_string(), 1);
+        game.card_miss_counts.insert("Card 4".to_string(), 20);
+        game.card_deck.clear();
+        game.replenish_deck();
+
+        let copies = |front: &str| game.card_deck.iter().filter(|card| card.front == front).count();
+        assert_eq!(copies("Card 2"), DECK_CARD_DUPLICATES as usize);
+        assert_eq!(copies("Card 1"), DECK_CARD_DUPLICATES as usize + 4);
+        assert_eq!(copies("Card 3"), DECK_CARD_DUPLICATES as usize + 3);
+        assert_eq!(copies("Card 4"), (DECK_CARD_DUPLICATES + MAX_MISS_EXTRA_DUPLICATES) as usize);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_spawned_cards_are_spread_out() {
+        for seed in 0..10 {
+            let mut game = new_game_for_test_large(600.0, 800.0, seed, GameMode::Normal, 1.0);
+            for _ in 0..2 {
+                game.spawn_card();
+            }
+            let xs: Vec<f64> = game.cards.iter().map(|card| card.x).collect();
+            assert_eq!(xs.len(), 3);
+            for (i, a) in xs.iter().enumerate() {
+                for b in &xs[i + 1..] {
+                    assert!((a - b).abs() >= CARD_WIDTH / 2.0, "seed {}: {:?}", seed, xs);
+                }
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn test_set_mode_reorients_falling_cards() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.cards = vec![
+            Card { id: 0, raw_front: "Shwmae".to_string(), raw_back: "Hello".to_string(), front: "Shwmae".to_string(), back: "Hello".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
+            Card { id: 1, raw_front: "Iawn".to_string(), raw_back: "Good".to_string(), front: "Iawn".to_string(), back: "Good".to_string(), x: 0.0, y: 750.0, flipped: true, time_since_flipped: Some(0.2), free_misses: 2, ..Default::default() },
+        ];
+
+        game.set_mode(GameMode::Reverse);
+        assert_eq!(game.get_mode(), GameMode::Reverse);
+        assert_eq!((game.cards[0].front.as_str(), game.cards[0].back.as_str()), ("Hello", "Shwmae"));
+        assert_eq!((game.cards[1].front.as_str(), game.cards[1].back.as_str()), ("Iawn", "Good"));
+        assert!(game.submit_answer("Shwmae"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_difficulty_getters_reflect_scoring() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 2.0);
+        assert_eq!(game.get_card_speed(), INITIAL_CARD_SPEED * 2.0);
+        assert_eq!(game.get_spawn_interval(), INITIAL_SPAWN_INTERVAL);
+
+        for i in 0..SCORE_PER_SPAWN_INTERVAL_DECREASE as u32 {
+            game.cards = vec![
+                Card { id: 100 + i, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
+            ];
+            assert!(game.submit_answer("A"));
+        }
+        assert!(game.get_card_speed() > INITIAL_CARD_SPEED * 2.0);
+        assert!(game.get_spawn_interval() < INITIAL_SPAWN_INTERVAL);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_anki_export_with_stats_tags_miss_counts() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        assert_eq!(game.generate_anki_export_with_stats(), "");
+        let missed = |id: u32, raw_front: &str, raw_back: &str| Card {
+            id,
+            raw_front: raw_front.to_string(),
+            raw_back: raw_back.to_string(),
+            front: raw_front.to_string(),
+            back: raw_back.to_string(),
+            flipped: true,
+            ..Default::default()
+        };
+        game.missed_cards = vec![missed(1, "Shwmae", "Hello"), missed(2, "Iawn", "Good"), missed(3, "Shwmae", "Hello")];
+        game.card_miss_counts.insert("Shwmae".to_string(), 2);
+        game.card_miss_counts.insert("Iawn".to_string(), 1);
+
+        assert_eq!(
+            game.generate_anki_export_with_stats(),
+            "#separator:tab\n#html:true\n#tags column:3\nShwmae\tHello\tmiss:2\nIawn\tGood\tmiss:1"
+        );
+    }
+
+    #[test]
+    fn test_parse_json_cards() {
+        let cards = parse_json_cards(r#"[{"front": "car(s)", "back": "Auto/Wagen"}, {"front": "Shwmae", "back": "Hello", "tags": ["welsh"]}]"#).unwrap();
+        let tab_cards = parse_cards("car(s)\tAuto/Wagen\nShwmae\tHello\t\twelsh");
+        assert_eq!(serde_json::to_string(&cards).unwrap(), serde_json::to_string(&tab_cards).unwrap());
+        assert_eq!(cards[0].front, "car / cars");
+        assert_eq!(cards[0].back, "Auto / Wagen");
+
+        let error = parse_json_cards(r#"[{"front": "missing back"}]"#).err().unwrap();
+        assert!(error.starts_with("Invalid JSON deck"), "{}", error);
+        assert!(parse_json_cards("not json").is_err());
+    }
+
+    #[test]
+    fn test_process_side_alternative_separators() {
+        let separators = ['/', ';', '|'];
+        assert_eq!(process_side("a; b", &separators), "a / b");
+        assert_eq!(process_side("a | b / a", &separators), "a / b");
+        let alternatives: HashSet<String> = process_side("car(s); auto", &separators).split(" / ").map(|s| s.to_string()).collect();
+        assert_eq!(alternatives, HashSet::from(["car".to_string(), "cars".to_string(), "auto".to_string()]));
+        assert_eq!(process_side(";", &separators), "");
+        assert_eq!(process_side("a;;b|", &separators), "a / b");
+    }
+
+    #[test]
+    fn test_extra_alternative_separators_are_opt_in() {
+        let cards = parse_cards("Rwy wedi blino\tI'm tired; let's go");
+        assert_eq!(cards[0].back, "I'm tired; let's go");
+
+        let cards = parse_cards("#separators:; |\nIawn\ta; b | c / d");
+        assert_eq!(cards[0].back, "a / b / c / d");
+        assert_eq!(detect_alternative_separators("#separators:;/"), vec!['/', ';']);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_semicolon_alternatives_are_accepted() {
+        let deck = parse_deck("#separators:;\nIawn\ta; b");
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, deck, JsValue::UNDEFINED).unwrap();
+        assert!(game.submit_answer("a"));
+        game.spawn_card();
+        assert!(game.submit_answer("b"));
+    }
+
+    #[test]
+    fn test_mask_answer() {
+        assert_eq!(mask_answer("Hello"), "H____");
+        assert_eq!(mask_answer("Good / Fine / Okay"), "G___");
+        assert_eq!(mask_answer("How are you?"), "H__ ___ ____");
+        assert_eq!(mask_answer(""), "");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_request_hint_masks_answer_and_reduces_score() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards = vec![
+            Card { id: 0, raw_front: "Iawn".to_string(), raw_back: "Good / Fine / Okay".to_string(), front: "Iawn".to_string(), back: "Good / Fine / Okay".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
+            Card { id: 1, raw_front: "Shwmae".to_string(), raw_back: "Hello".to_string(), front: "Shwmae".to_string(), back: "Hello".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
+        ];
+
+        assert_eq!(game.request_hint(0), None);
+        game.cards[0].age = HINT_DELAY_SECONDS;
+        let hint = game.request_hint(0).unwrap();
+        assert!(hint.starts_with('G'));
+        assert_eq!(hint.chars().count(), "Good".len());
+        assert_eq!(game.request_hint(42), None);
+
+        assert!(game.submit_answer("Fine"));
+        assert_eq!(game.get_score(), 0);
+        assert!(game.submit_answer("Hello"));
+        assert!(game.get_score() > 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_set_max_cards_cap() {
+        let mut game = new_game_for_test(600.0, 100_000.0, 0, GameMode::Normal, 1.0);
+        game.set_max_cards_cap(2);
+        game.score = 1000;
+        game.card_spawn_interval = 0.1;
+        for _ in 0..50 {
+            game.tick(0.2);
+            assert!(game.cards.len() <= 2);
+        }
+        assert_eq!(game.cards.len(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_quick_clears_build_combo() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.card_spawn_interval = 1_000_000.0;
+        game.combo_streak = 2;
+        let card = |id: u32| Card { id, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() };
+
+        game.cards = vec![card(0)];
+        assert!(game.submit_answer("A"));
+        let first_points = game.get_score();
+        game.tick(0.5);
+        game.cards = vec![card(1)];
+        assert!(game.submit_answer("A"));
+        let second_points = game.get_score() - first_points;
+        assert!(second_points > first_points);
+        let combo: ComboState = serde_wasm_bindgen::from_value(game.get_combo()).unwrap();
+        assert_eq!(combo.multiplier, 2);
+        assert_eq!(combo.seconds_left, COMBO_WINDOW_SECONDS);
+        // The combo only boosts the displayed score
+        assert_eq!(game.get_raw_score(), 2);
+    }
+
+    #[test]
+    fn test_combo_does_not_speed_up_unlocks() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        game.card_spawn_interval = 1_000_000.0;
+        let card = |id: u32| Card { id, raw_front: "Q".to_string(), back: "A".to_string(), ..Default::default() };
+        game.combo_streak = COMBO_STREAK_PER_MULTIPLIER * (MAX_COMBO_MULTIPLIER - 1);
+        for id in 0..4 {
+            game.cards = vec![card(id)];
+            assert!(game.submit_answer("A"));
+        }
+        assert_eq!(game.get_raw_score(), 4);
+        assert_eq!(game.get_score(), 4 * MAX_COMBO_MULTIPLIER as i32);
+        assert_eq!(game.unlock_progress().unlocked, INITIAL_UNLOCKED_CARDS);
+        assert_eq!(game.unlock_progress().score_to_next_unlock, Some(SCORE_PER_CARD_UNLOCK - 4));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_combo_decays_without_correct_answers() {
+        let mut game = new_game_for_test(600.0, 100_000.0, 0, GameMode::Normal, 1.0);
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
+        ];
+        assert!(game.submit_answer("A"));
+        game.combo_streak = 4;
+        game.tick(COMBO_WINDOW_SECONDS - 1.0);
+        assert_eq!(game.get_combo_streak(), 4);
+        game.tick(2.0);
+        assert_eq!(game.get_combo_streak(), 0);
+        let combo: ComboState = serde_wasm_bindgen::from_value(game.get_combo()).unwrap();
+        assert_eq!(combo, ComboState { streak: 0, multiplier: 1, seconds_left: 0.0 });
+    }
+
+    #[test]
+    fn test_check_card_data() {
+        let card = |front: &str, back: &str| CustomCard { front: front.to_string(), back: back.to_string(), ..Default::default() };
+        assert_eq!(check_card_data(&[]), Err("Custom deck cannot be empty.".to_string()));
+        assert_eq!(check_card_data(&[card("Q", "A"), card("  ", "B")]), Err("Card 1 has an empty front.".to_string()));
+        assert_eq!(check_card_data(&[card("Q", "")]), Err("Card 0 has an empty back.".to_string()));
+        assert_eq!(check_card_data(&parse_cards(TEST_CARD_DATA)), Ok(()));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_new_rejects_blank_cards() {
+        let deck = |cards: Vec<CustomCard>| serde_wasm_bindgen::to_value(&cards).unwrap();
+        let err = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, deck(vec![]), JsValue::UNDEFINED).err().unwrap();
+        assert_eq!(err.as_string().unwrap(), "Custom deck cannot be empty.");
+        let blank_front = vec![CustomCard { front: " ".to_string(), back: "A".to_string(), ..Default::default() }];
+        let err = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, deck(blank_front), JsValue::UNDEFINED).err().unwrap();
+        assert_eq!(err.as_string().unwrap(), "Card 0 has an empty front.");
+        assert!(Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), JsValue::UNDEFINED).is_ok());
+    }
+
+    #[test]
+    fn test_zen_is_an_alias_of_practice() {
+        let config: GameConfig = serde_json::from_str(r#"{"zen": true}"#).unwrap();
+        assert!(config.practice);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_zen_mode_never_loses_health() {
+        let config = GameConfig { practice: true, ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        assert!(game.is_zen());
+        for _ in 0..600 {
+            game.tick(0.5);
+        }
+        assert_eq!(game.get_health(), INITIAL_HEALTH);
+        assert!(!game.is_game_over());
+        assert!(game.card_miss_counts.values().sum::<u32>() > 10);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_unlock_all_makes_every_card_available() {
+        let config = GameConfig { deck: DeckConfig { unlock_all: true, ..DeckConfig::default() }, ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA_LARGE), config_jsvalue).unwrap();
+        let all_fronts: HashSet<String> = game.card_data.iter().map(|c| c.front.clone()).collect();
+        assert!(all_fronts.len() > INITIAL_UNLOCKED_CARDS);
+
+        game.replenish_deck();
+        let deck_fronts: HashSet<String> = game.card_deck.iter().map(|c| c.front.clone()).collect();
+        assert_eq!(deck_fronts, all_fronts);
+
+        // Display marks cards unlocked by their index in the available cards
+        assert_eq!(game.get_available_cards_data().len(), game.card_data.len());
+
+        game.restart();
+        assert_eq!(game.get_available_cards_data().len(), all_fronts.len());
+    }
+
+    #[test]
+    fn test_expand_parens_simple() {
+        assert_eq!(expand_parens("card(s)"), vec!["card", "cards"]);
+        assert_eq!(expand_parens("(the) cat"), vec!["cat", "the cat"]);
+    }
+
+    #[test]
+    fn test_expand_parens_nested() {
+        assert_eq!(expand_parens("a(b(c))"), vec!["a", "ab", "abc"]);
+        assert_eq!(expand_parens("(x)y(z)"), vec!["y", "yz", "xy", "xyz"]);
+    }
+
+    #[test]
+    fn test_expand_parens_escaped_and_unbalanced() {
+        assert_eq!(expand_parens(r"f\(x\)"), vec!["f(x)"]);
+        assert_eq!(expand_parens(r"a(\(b\))"), vec!["a", "a(b)"]);
+        assert_eq!(expand_parens("a(b"), vec!["a(b"]);
+        assert_eq!(expand_parens("a)b"), vec!["a)b"]);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_deck_progress_counts_mastered_cards() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.card_success_counts.insert("Shwmae".to_string(), DECK_CARD_DUPLICATES);
+        game.card_success_counts.insert("Iawn".to_string(), 1);
+        game.card_miss_counts.insert("Iawn".to_string(), 2);
+
+        let progress: DeckProgress = serde_wasm_bindgen::from_value(game.get_deck_progress()).unwrap();
+        assert_eq!(progress.cards.len(), 3);
+        assert_eq!(progress.mastered, 1);
+        assert_eq!(progress.in_progress, 2);
+        let iawn = progress.cards.iter().find(|c| c.raw_front == "Iawn").unwrap();
+        assert_eq!((iawn.success_count, iawn.miss_count, iawn.mastered), (1, 2, false));
+        // One miss beyond its successes earns Iawn an extra copy
+        assert_eq!(iawn.duplicates, DECK_CARD_DUPLICATES + 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_replay_reproduces_session() {
+        let mut game = new_game_for_test_large(600.0, 800.0, 5, GameMode::Both, 1.0);
+        for step in 0..40 {
+            game.tick(0.3);
+            if step % 7 == 0 {
+                if let Some(answer) = game.cards.iter().find(|c| !c.flipped).map(|c| c.back.clone()) {
+                    game.submit_answer(&answer);
+                }
+            }
+            if step == 20 {
+                if let Some(id) = game.cards.iter().find(|c| !c.flipped).map(|c| c.id) {
+                    game.skip_card(id);
+                }
+            }
+            if step == 25 {
+                game.set_mode(GameMode::Reverse);
+            }
+            if step == 28 {
+                if let Some((id, answer)) = game.cards.iter().find(|c| !c.flipped && c.age >= HINT_DELAY_SECONDS).map(|c| (c.id, c.back.clone())) {
+                    assert!(game.request_hint(id).is_some());
+                    game.submit_answer(&answer);
+                }
+            }
+        }
+        game.submit_answer("wrong");
+
+        let replayed = Game::replay(5, GameMode::Both, parse_deck(TEST_CARD_DATA_LARGE), &game.export_replay()).unwrap();
+        let card_ids = |g: &Game| g.cards.iter().map(|c| c.id).collect::<Vec<_>>();
+        assert!(game.get_score() > 0);
+        assert_eq!(replayed.get_score(), game.get_score());
+        assert_eq!(card_ids(&replayed), card_ids(&game));
+        assert_eq!(replayed.replay_log, game.replay_log);
+    }
+
+    #[test]
+    fn test_replay_starts_from_the_initial_config() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 5, GameMode::Normal, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        for step in 0..40 {
+            game.tick(0.3);
+            if step == 10 {
+                game.set_max_cards_cap(1);
+                game.set_flip_display_seconds(0.5);
+                game.set_burst_probability(1.0);
+                game.set_bottom_margin(100.0);
+                game.set_blitz_acceleration(2.0);
+            }
+            if step == 15 {
+                game.set_mode(GameMode::Reverse);
+                if let Some((id, answer)) = game.cards.iter().find(|c| !c.flipped && c.age >= HINT_DELAY_SECONDS).map(|c| (c.id, c.back.clone())) {
+                    assert!(game.request_hint(id).is_some());
+                    game.submit_answer(&answer);
+                }
+            }
+        }
+
+        let replayed = Game::replay_with_deck(5, GameMode::Normal, parse_cards(TEST_CARD_DATA_LARGE), &game.export_replay()).unwrap();
+        let card_fronts = |g: &Game| g.cards.iter().map(|c| (c.id, c.front.clone(), c.y)).collect::<Vec<_>>();
+        assert_eq!(replayed.replay_config.max_cards_cap, None);
+        assert_eq!(replayed.config.max_cards_cap, Some(1));
+        assert_eq!(replayed.get_score(), game.get_score());
+        assert_eq!(replayed.get_health(), game.get_health());
+        assert_eq!(card_fronts(&replayed), card_fronts(&game));
+        assert_eq!(replayed.replay_log, game.replay_log);
+    }
+
+    #[test]
+    fn test_replay_reproduces_deck_changes() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 5, GameMode::Normal, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        let next_front = game.card_deck.last().unwrap().front.clone();
+        game.exclude_card(&next_front);
+        game.apply_stats(HashMap::from([("Card 2".to_string(), 3)]), HashMap::from([("Card 3".to_string(), 2)]));
+        for step in 0..200 {
+            game.tick(0.3);
+            if let Some(answer) = game.cards.iter().find(|c| !c.flipped).map(|c| c.back.clone()) {
+                game.submit_answer(&answer);
+            }
+            if step == 100 {
+                game.include_card(&next_front);
+            }
+        }
+
+        let replayed = Game::replay_with_deck(5, GameMode::Normal, parse_cards(TEST_CARD_DATA_LARGE), &game.export_replay()).unwrap();
+        let cards = |g: &Game| g.cards.iter().map(|c| (c.id, c.front.clone(), c.x)).collect::<Vec<_>>();
+        assert!(game.score > 0);
+        assert_eq!(replayed.score, game.score);
+        assert_eq!(replayed.deck_round, game.deck_round);
+        assert_eq!(cards(&replayed), cards(&game));
+        assert_eq!(replayed.replay_log, game.replay_log);
+    }
+
+    #[test]
+    fn test_replay_reproduces_undone_misses() {
+        let config = GameConfig { free_misses_for_new_cards: Some(0), ..GameConfig::default() };
+        let mut game = Game::with_deck(600.0, 200.0, 3, GameMode::Normal, 1.0, parse_cards(TEST_CARD_DATA), config).unwrap();
+        while game.last_miss.is_none() {
+            game.tick(0.1);
+        }
+        let health = game.get_health();
+        assert!(game.undo_last_miss());
+        assert_eq!(game.get_health(), health + 1);
+        game.tick(0.1);
+
+        let replayed = Game::replay_with_deck(3, GameMode::Normal, parse_cards(TEST_CARD_DATA), &game.export_replay()).unwrap();
+        assert_eq!(replayed.get_health(), game.get_health());
+        assert_eq!(replayed.missed_cards.len(), game.missed_cards.len());
+        assert_eq!(replayed.card_miss_counts, game.card_miss_counts);
+        assert_eq!(replayed.replay_log, game.replay_log);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_spawn_skips_fronts_already_falling() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        let custom = |front: &str| CustomCard { front: front.to_string(), back: "A".to_string(), ..Default::default() };
+        game.cards = vec![
+            Card { id: 0, raw_front: "Shwmae".to_string(), raw_back: "Hello".to_string(), front: "Shwmae".to_string(), back: "Hello".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() },
+        ];
+        game.card_deck = vec![custom("Iawn"), custom("Shwmae"), custom("Shwmae")];
+        game.spawn_card();
+        let fronts: HashSet<&str> = game.cards.iter().filter(|c| !c.flipped).map(|c| c.raw_front.as_str()).collect();
+        assert_eq!(fronts.len(), game.cards.len());
+        assert_eq!(game.cards[1].raw_front, "Iawn");
+
+        // Only duplicates left: spawn anyway instead of stalling
+        game.spawn_card();
+        assert_eq!(game.cards.len(), 3);
+        assert_eq!(game.card_deck.len(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_cards_for_render_sorted_by_height() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        let card = |id: u32, y: f64, flipped: bool| Card { id, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), y, flipped, ..Default::default() };
+        game.cards = vec![card(0, 300.0, false), card(1, 750.0, true), card(2, 100.0, false), card(3, 300.0, false)];
+
+        let ids = |cards: Vec<RenderableCard>| cards.iter().map(|c| c.id).collect::<Vec<_>>();
+        assert_eq!(ids(game.cards_for_render(true)), vec![2, 0, 3, 1]);
+        assert_eq!(ids(game.cards_for_render(false)), vec![2, 0, 3]);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_flipped_card_lifetime_config() {
+        let config: GameConfig = serde_json::from_str(r#"{"flipped_card_lifetime": 3.0}"#).unwrap();
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: true, time_since_flipped: Some(0.0), free_misses: 2, ..Default::default() },
+        ];
+        game.tick(1.5);
+        assert_eq!(game.cards.len(), 1);
+
+        game.restart();
+        assert_eq!(game.get_flip_display_seconds(), 3.0);
+    }
+
+    #[test]
+    fn test_parse_cards_tags_column_header() {
+        let text = "#separator:tab\n#tags column:3\nHund\tdog\tanimals german\nKatze\tcat\tanimals\nHaus\thouse\n";
+        let cards = parse_cards(text);
+        assert_eq!(cards.len(), 3);
+        assert_eq!(cards[0].tags, vec!["animals".to_string(), "german".to_string()]);
+        assert_eq!(cards[0].hint, None);
+        assert_eq!(cards[1].tags, vec!["animals".to_string()]);
+        assert!(cards[2].tags.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_tag_filter_limits_card_data() {
+        let deck = "#tags column:3\nHund\tdog\tanimals german\nKatze\tcat\tanimals\nHaus\thouse\tgerman\nBaum\ttree\n";
+        let config = GameConfig { tag_filter: vec!["german".to_string()], ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(deck), config_jsvalue).unwrap();
+        let fronts: Vec<&str> = game.card_data.iter().map(|c| c.front.as_str()).collect();
+        assert_eq!(fronts, vec!["Hund", "Haus"]);
+
+        let unfiltered = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(deck), JsValue::UNDEFINED).unwrap();
+        assert_eq!(unfiltered.card_data.len(), 4);
+
+        let config = GameConfig { tag_filter: vec!["plants".to_string()], ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        assert!(Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(deck), config_jsvalue).is_err());
+    }
+
+    #[test]
+    fn test_normalize_ignoring_word_order() {
+        let options = NormalizeOptions { ignore_word_order: true, ..NormalizeOptions::default() };
+        assert_eq!(normalize_with("the quick brown fox", options), normalize_with("Fox, brown quick the!", options));
+        assert_ne!(normalize_string("the quick brown fox"), normalize_string("fox brown quick the"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_ignore_word_order_accepts_reordered_words() {
+        let fox = || vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "the quick brown fox / hound".to_string(), front: "Q".to_string(), back: "the quick brown fox / hound".to_string(), ..Default::default() },
+        ];
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.cards = fox();
+        assert!(!game.submit_answer("brown fox the quick"));
+
+        let config = GameConfig { ignore_word_order: true, ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        game.cards = fox();
+        // Words are not mixed across alternatives
+        assert!(!game.submit_answer("hound fox quick brown the"));
+        assert!(game.submit_answer("brown fox the quick"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_damage_cooldown_limits_clustered_damage() {
+        let config = GameConfig { damage_cooldown_seconds: 2.0, ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        game.card_spawn_interval = 1_000_000.0;
+        let card = |id: u32, raw_front: &str| Card { id, raw_front: raw_front.to_string(), raw_back: "A".to_string(), front: raw_front.to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() };
+        game.cards = vec![card(0, "Q1"), card(1, "Q2")];
+        let time_to_flip = (800.0 - CARD_HEIGHT) / game.card_speed;
+        game.tick(time_to_flip + 0.1);
+        assert_eq!(game.get_health(), INITIAL_HEALTH - 1);
+        assert_eq!(game.card_miss_counts["Q1"] + game.card_miss_counts["Q2"], 2);
+
+        // Once the window has passed, misses hurt again
+        game.tick(2.0);
+        game.cards = vec![card(2, "Q3")];
+        game.tick(time_to_flip + 0.1);
+        assert_eq!(game.get_health(), INITIAL_HEALTH - 2);
+    }
+
+    #[test]
+    fn test_undo_last_miss_restores_health_and_count() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA).unwrap();
+        assert!(!game.undo_last_miss());
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        game.card_boxes.insert("Q".to_string(), 3);
+        let mut schedule = CardSchedule::default();
+        schedule.record_success(0);
+        schedule.record_success(1);
+        game.card_schedules.insert("Q".to_string(), schedule.clone());
+        game.current_streak = 4;
+        let time_to_flip = (800.0 - CARD_HEIGHT) / game.card_speed;
+        game.tick(time_to_flip + 0.1);
+        assert_eq!(game.get_health(), INITIAL_HEALTH - 1);
+        assert_eq!(game.card_miss_counts["Q"], 1);
+        assert_eq!(game.card_boxes["Q"], 0);
+        assert_eq!(game.current_streak, 0);
+
+        game.tick(0.5);
+        assert!(game.undo_last_miss());
+        assert_eq!(game.get_health(), INITIAL_HEALTH);
+        assert_eq!(game.card_miss_counts["Q"], 0);
+        assert!(game.missed_cards.is_empty());
+        assert_eq!(game.card_boxes["Q"], 3);
+        let restored = &game.card_schedules["Q"];
+        assert_eq!((restored.interval, restored.ease, restored.due_round), (schedule.interval, schedule.ease, schedule.due_round));
+        assert_eq!(game.current_streak, 4);
+        assert!(!game.undo_last_miss());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_undo_last_miss_expires() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards = vec![
+            Card { id: 0, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 0, ..Default::default() },
+        ];
+        let time_to_flip = (800.0 - CARD_HEIGHT) / game.card_speed;
+        game.tick(time_to_flip + 0.1);
+        game.tick(UNDO_MISS_WINDOW_SECONDS + 0.5);
+        assert!(!game.undo_last_miss());
+        assert_eq!(game.get_health(), INITIAL_HEALTH - 1);
+    }
+
+    #[test]
+    fn test_normalize_answer_matches_internal_normalization() {
+        assert_eq!(normalize_answer("crème brûlée"), normalize_string("crème brûlée"));
+        assert_eq!(normalize_answer("  Crème, Brûlée! "), "creme brulee");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_peek_next_cards_matches_spawn() {
+        let mut game = new_game_for_test_large(600.0, 800.0, 0, GameMode::Reverse, 1.0);
+        let deck_len = game.card_deck.len();
+        let upcoming: Vec<UpcomingCard> = serde_wasm_bindgen::from_value(game.peek_next_cards(3)).unwrap();
+        assert_eq!(upcoming.len(), 3);
+        assert_eq!(game.card_deck.len(), deck_len);
+
+        game.cards.clear();
+        game.spawn_card();
+        assert_eq!(game.cards[0].front, upcoming[0].front);
+        assert_eq!(game.cards[0].back, upcoming[0].back);
+
+        let all: Vec<UpcomingCard> = serde_wasm_bindgen::from_value(game.peek_next_cards(1000)).unwrap();
+        assert_eq!(all.len(), game.card_deck.len());
+    }
+
+    #[test]
+    fn test_upcoming_cards_skip_fronts_already_falling() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 0, GameMode::Reverse, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        let last = game.card_deck.last().unwrap().clone();
+        game.cards = vec![Card { raw_front: last.front.clone(), front: last.back.clone(), back: last.front.clone(), ..Default::default() }];
+        let upcoming = game.upcoming_cards(3);
+        assert_eq!(upcoming.len(), 3);
+        assert_ne!(upcoming[0].back, last.front);
+
+        game.spawn_card();
+        assert_eq!(game.cards[1].front, upcoming[0].front);
+        assert_eq!(game.cards[1].back, upcoming[0].back);
+        assert_eq!(game.upcoming_cards(1000).len(), game.card_deck.len());
+    }
+
+    #[test]
+    fn test_hints_are_dropped_with_their_cards() {
+        let mut game = Game::from_deck_text(600.0, 100_000.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA).unwrap();
+        game.card_spawn_interval = 1_000_000.0;
+        game.spawn_card();
+        let (first, second) = (game.cards[0].id, game.cards[1].id);
+        assert_eq!(game.request_hint(first), None);
+        game.cards.iter_mut().for_each(|card| card.age = HINT_DELAY_SECONDS);
+        assert!(game.request_hint(first).is_some());
+        assert!(game.request_hint(second).is_some());
+
+        assert!(game.skip_card(first));
+        assert!(!game.hints_used.contains(&first));
+        game.cards[0].y = 100_000.0;
+        game.tick(0.01);
+        assert!(game.cards[0].flipped);
+        assert!(game.hints_used.is_empty());
+
+        game.spawn_card();
+        let id = game.cards.iter().find(|card| !card.flipped).unwrap().id;
+        game.cards.iter_mut().for_each(|card| card.age = HINT_DELAY_SECONDS);
+        game.pause();
+        assert_eq!(game.request_hint(id), None);
+        game.resume();
+        game.give_up();
+        assert_eq!(game.request_hint(id), None);
+        assert!(game.hints_used.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_build_review_deck_after_misses() {
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.card_spawn_interval = 1_000_000.0;
+        let card = |id: u32, raw_front: &str, raw_back: &str| Card { id, raw_front: raw_front.to_string(), raw_back: raw_back.to_string(), front: raw_front.to_string(), back: raw_back.to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() };
+        let time_to_flip = (800.0 - CARD_HEIGHT) / game.card_speed;
+        for id in 0..2 {
+            game.cards = vec![card(2 * id, "Shwmae", "Hello"), card(2 * id + 1, "Iawn", "Good / Fine / Okay")];
+            game.tick(time_to_flip + 0.1);
+        }
+
+        let deck: Vec<CustomCard> = serde_wasm_bindgen::from_value(game.build_review_deck()).unwrap();
+        let fronts: Vec<&str> = deck.iter().map(|c| c.front.as_str()).collect();
+        assert_eq!(fronts, vec!["Shwmae", "Iawn"]);
+    }
+
+    #[test]
+    fn test_game_config_health_checks() {
+        let health = |initial, max| GameConfig { initial_health: Some(initial), max_health: Some(max), ..GameConfig::default() };
+        assert_eq!(GameConfig::default().check(), Ok(()));
+        assert_eq!(health(5, 10).check(), Ok(()));
+        assert!(health(0, 5).check().is_err());
+        assert!(health(3, 0).check().is_err());
+        assert_eq!(health(6, 5).check(), Err("Initial health 6 cannot exceed max health 5.".to_string()));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_configured_health_survives_restart() {
+        let config = GameConfig { initial_health: Some(5), max_health: Some(10), ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        assert_eq!((game.get_health(), game.get_max_health()), (5, 10));
+        game.health = 1;
+        game.restart();
+        assert_eq!((game.get_health(), game.get_max_health()), (5, 10));
+
+        let config = GameConfig { initial_health: Some(6), max_health: Some(5), ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        assert!(Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_average_response_time() {
+        let mut game = new_game_for_test(600.0, 100_000.0, 0, GameMode::Normal, 1.0);
+        assert_eq!(game.get_average_response_time(), 0.0);
+        game.card_spawn_interval = 1_000_000.0;
+        let card = |id: u32| Card { id, raw_front: "Q".to_string(), raw_back: "A".to_string(), front: "Q".to_string(), back: "A".to_string(), x: 0.0, y: 0.0, flipped: false, time_since_flipped: None, free_misses: 2, ..Default::default() };
+
+        game.cards = vec![card(0)];
+        game.tick(1.0);
+        game.tick(1.0);
+        assert!(game.submit_answer("A"));
+        assert!((game.get_average_response_time() - 2.0).abs() < 1e-9);
+
+        game.cards = vec![card(1)];
+        game.tick(1.0);
+        assert!(game.submit_answer("A"));
+        assert!((game.get_average_response_time() - 1.5).abs() < 1e-9);
+
+        game.restart();
+        assert_eq!(game.get_average_response_time(), 0.0);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_play_time_excludes_pauses() {
+        let mut game = new_game_for_test(600.0, 100_000.0, 0, GameMode::Normal, 1.0);
+        game.tick(1.5);
+        game.pause();
+        game.tick(10.0);
+        game.resume();
+        game.tick(2.0);
+        assert!((game.get_play_time() - 3.5).abs() < 1e-9);
+
+        game.restart();
+        assert_eq!(game.get_play_time(), 0.0);
+    }
+
+    #[test]
+    fn test_normalize_drops_stopwords() {
+        let stopwords = vec!["the".to_string(), "A".to_string()];
+        let options = NormalizeOptions { stopwords: &stopwords, ..NormalizeOptions::default() };
+        assert_eq!(normalize_with("The house", options), "house");
+        assert_eq!(normalize_with("a house", options), "house");
+        assert_eq!(normalize_with("the", options), "the");
+        assert_eq!(normalize_string("the house"), "the house");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_stopwords_allow_leaving_out_function_words() {
+        let house = || vec![
+            Card { id: 0, raw_front: "ty".to_string(), raw_back: "house".to_string(), front: "ty".to_string(), back: "house".to_string(), ..Default::default() },
+        ];
+        let mut game = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        game.cards = house();
+        assert!(!game.submit_answer("the house"));
+
+        let config = GameConfig { stopwords: vec!["the".to_string()], ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        game.cards = house();
+        assert!(game.submit_answer("the house"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_reverse_mode_accepts_front_synonyms() {
+        let deck = "hi / hello; hey\tshwmae";
+        for answer in ["hi", "hello", "Hey!"] {
+            let mut game = Game::new(600.0, 800.0, 0, GameMode::Reverse, 1.0, parse_deck(deck), JsValue::UNDEFINED).unwrap();
+            assert_eq!(game.cards[0].front, "shwmae");
+            assert_eq!(game.cards[0].back, "hi / hello / hey");
+            assert!(game.submit_answer(answer), "{} should clear the card", answer);
+        }
+
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Reverse, 1.0, parse_deck(deck), JsValue::UNDEFINED).unwrap();
+        assert!(!game.submit_answer("hi hello"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_burst_spawns_several_cards_at_once() {
+        let mut game = new_game_for_test_large(600.0, 100_000.0, 0, GameMode::Normal, 1.0);
+        game.set_burst_probability(1.0);
+        game.score = 1000;
+        game.cards.clear();
+        game.tick(game.card_spawn_interval + 0.1);
+        let spawned = game.cards.len();
+        assert!((MIN_BURST_SIZE..=MAX_BURST_SIZE).contains(&spawned));
+
+        // Respects the card limit
+        game.set_max_cards_cap(spawned + 1);
+        game.tick(game.card_spawn_interval + 0.1);
+        assert_eq!(game.cards.len(), spawned + 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_spawn_columns_snap_cards_to_column_centers() {
+        let config = GameConfig { spawn_columns: Some(3), ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(1200.0, 100_000.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA_LARGE), config_jsvalue).unwrap();
+        game.score = 1000;
+        game.card_spawn_interval = 0.1;
+        for _ in 0..30 {
+            game.tick(0.2);
+        }
+        let range = 1200.0 - CARD_WIDTH;
+        let centers: Vec<f64> = (0..3).map(|column| Game::column_center(column, 3, range)).collect();
+        assert!(game.cards.len() > 3);
+        for card in &game.cards {
+            assert!(centers.contains(&card.x), "x {} is not a column center", card.x);
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn test_excluded_card_never_spawns_until_included() {
+        let mut game = new_game_for_test(600.0, 100_000.0, 0, GameMode::Normal, 1.0);
+        game.exclude_card("Shwmae");
+        for _ in 0..5 {
+            game.replenish_deck();
+            assert!(game.card_deck.iter().all(|card| card.front != "Shwmae"));
+        }
+        for _ in 0..20 {
+            game.cards.clear();
+            game.spawn_card();
+            assert!(game.cards.iter().all(|card| card.raw_front != "Shwmae"));
+        }
+
+        game.include_card("Shwmae");
+        assert!(game.card_deck.iter().any(|card| card.front == "Shwmae"));
+    }
+
+    #[test]
+    fn test_include_card_keeps_the_deck_and_round() {
+        let mut game = Game::from_deck_text(600.0, 100_000.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA).unwrap();
+        game.exclude_card("Shwmae");
+        game.replenish_deck();
+        let round = game.deck_round;
+        let rest: Vec<String> = game.card_deck.iter().map(|card| card.front.clone()).collect();
+
+        game.include_card("Shwmae");
+        let fronts: Vec<String> = game.card_deck.iter().map(|card| card.front.clone()).collect();
+        assert_eq!(game.deck_round, round);
+        assert_eq!(fronts.iter().filter(|front| *front == "Shwmae").count() as u32, game.deck_duplicates_for("Shwmae"));
+        assert_eq!(fronts.into_iter().filter(|front| front != "Shwmae").collect::<Vec<_>>(), rest);
+
+        for front in ["Shwmae", "Sut wyt ti?", "Iawn"] {
+            game.exclude_card(front);
+        }
+        game.replenish_deck();
+        assert_eq!(game.deck_round, round);
+        assert!(game.card_deck.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_get_card_by_id() {
+        let mut game = new_game_for_test(600.0, 100_000.0, 0, GameMode::Normal, 1.0);
+        game.spawn_card();
+        let card = game.cards.last().unwrap();
+        let (id, front) = (card.id, card.front.clone());
+
+        let found: serde_json::Value = serde_wasm_bindgen::from_value(game.get_card_by_id(id)).unwrap();
+        assert_eq!(found["id"], id);
+        assert_eq!(found["front"], front.as_str());
+        assert!(game.get_card_by_id(9999).is_null());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_easy_difficulty_is_slower_and_has_more_health_than_hard() {
+        let new_game = |difficulty| {
+            let config = GameConfig { difficulty, ..GameConfig::default() };
+            let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+            Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap()
+        };
+        let easy = new_game(Difficulty::Easy);
+        let hard = new_game(Difficulty::Hard);
+        assert_eq!(easy.get_difficulty(), Difficulty::Easy);
+        assert!(easy.card_spawn_interval > hard.card_spawn_interval);
+        assert!(easy.card_speed < hard.card_speed);
+        assert!(easy.health > hard.health);
+
+        let normal = new_game_for_test(600.0, 800.0, 0, GameMode::Normal, 1.0);
+        assert_eq!(normal.get_difficulty(), Difficulty::Normal);
+        assert_eq!(normal.card_spawn_interval, INITIAL_SPAWN_INTERVAL);
+        assert_eq!(normal.health, INITIAL_HEALTH);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_bottom_margin_flips_cards_higher() {
+        let height = 800.0;
+        let config = GameConfig { bottom_margin: 100.0, ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, height, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        game.card_spawn_interval = 1_000_000.0;
+        let flip_y = height - CARD_HEIGHT - 100.0;
+
+        game.tick(flip_y / game.card_speed - 0.1);
+        assert!(!game.cards[0].flipped);
+        game.tick(0.2);
+        assert!(game.cards[0].flipped);
+        assert_eq!(game.cards[0].y, flip_y);
+
+        game.restart();
+        assert_eq!(game.config.bottom_margin, 100.0);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_new_with_preset_sets_spawn_interval_and_survives_restart() {
+        let new_game = |preset| Game::new_with_preset(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), preset).unwrap();
+        let easy = new_game(Difficulty::Easy);
+        let mut expert = new_game(Difficulty::Expert);
+        assert!(easy.card_spawn_interval > expert.card_spawn_interval);
+        assert!(expert.card_speed > easy.card_speed);
+
+        expert.restart();
+        assert_eq!(expert.get_difficulty(), Difficulty::Expert);
+        assert_eq!(expert.card_spawn_interval, Difficulty::Expert.initial_spawn_interval());
+
+        let normal = new_game(Difficulty::Normal);
+        assert_eq!(normal.card_spawn_interval, INITIAL_SPAWN_INTERVAL);
+        assert_eq!(normal.card_speed, INITIAL_CARD_SPEED);
+        assert_eq!(normal.max_health, MAX_HEALTH);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_free_misses_shrink_with_interactions() {
+        let config = GameConfig { free_misses_for_new_cards: Some(3), ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck("Q\tA"), config_jsvalue).unwrap();
+        let mut free_misses_after = |successes, misses| {
+            game.card_success_counts.insert("Q".to_string(), successes);
+            game.card_miss_counts.insert("Q".to_string(), misses);
+            game.cards.clear();
+            game.spawn_card();
+            game.cards[0].free_misses
+        };
+        assert_eq!(free_misses_after(0, 0), 3);
+        assert_eq!(free_misses_after(1, 0), 2);
+        assert_eq!(free_misses_after(3, 2), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_score_signature_matches_for_identical_playthroughs() {
+        let play = || {
+            let mut game = new_game_for_test(600.0, 800.0, 7, GameMode::Normal, 1.0);
+            for _ in 0..3 {
+                let answer = game.cards[0].back.split('/').next().unwrap().to_string();
+                assert!(game.submit_answer(&answer));
+                game.tick(game.card_spawn_interval + 0.1);
+            }
+            game
+        };
+        let mut first = play();
+        let second = play();
+        assert_eq!(first.score_signature(), second.score_signature());
+        assert_eq!(first.score_signature().len(), 16);
+
+        first.score += 1;
+        assert_ne!(first.score_signature(), second.score_signature());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_card_latency_is_recorded_on_clear() {
+        let mut game = new_game_for_test(600.0, 100_000.0, 0, GameMode::Normal, 1.0);
+        game.card_spawn_interval = 1_000_000.0;
+        let raw_front = game.cards[0].raw_front.clone();
+        let answer = game.cards[0].back.split('/').next().unwrap().to_string();
+        game.tick(1.5);
+        assert!(game.submit_answer(&answer));
+
+        let latencies: HashMap<String, f64> = serde_wasm_bindgen::from_value(game.get_card_latencies()).unwrap();
+        assert_eq!(latencies.len(), 1);
+        assert!((latencies[&raw_front] - 1.5).abs() < 1e-9);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_active_card_count_and_deck_remaining() {
+        let mut game = new_game_for_test(600.0, 100_000.0, 0, GameMode::Normal, 1.0);
+        assert_eq!(game.get_active_card_count(), 1);
+        let deck_remaining = game.get_deck_remaining();
+        assert_eq!(deck_remaining, 3 * DECK_CARD_DUPLICATES as usize - 1);
+
+        game.spawn_card();
+        assert_eq!(game.get_active_card_count(), 2);
+        assert_eq!(game.get_deck_remaining(), deck_remaining - 1);
+
+        let answer = game.cards[0].back.split('/').next().unwrap().to_string();
+        assert!(game.submit_answer(&answer));
+        assert!(game.get_active_card_count() < 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_blitz_cards_fall_faster_near_the_bottom() {
+        let mut game = new_game_for_test(600.0, 1000.0, 0, GameMode::Normal, 1.0);
+        game.set_blitz_acceleration(2.0);
+        game.card_spawn_interval = 1_000_000.0;
+        game.cards = vec![
+            Card { id: 100, y: 0.0, ..Default::default() },
+            Card { id: 101, y: 800.0, ..Default::default() },
+        ];
+        game.tick(0.1);
+        let top_distance = game.cards[0].y;
+        let bottom_distance = game.cards[1].y - 800.0;
+        assert!(bottom_distance > top_distance);
+
+        let mut steady = new_game_for_test(600.0, 1000.0, 0, GameMode::Normal, 1.0);
+        steady.card_spawn_interval = 1_000_000.0;
+        steady.cards = vec![Card { id: 101, y: 800.0, ..Default::default() }];
+        steady.tick(0.1);
+        assert_eq!(steady.cards[0].y - 800.0, steady.card_speed * 0.1);
+    }
+
+    #[test]
+    fn test_simulate_with_perfect_accuracy_survives() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        let result = game.simulate(1.0, 2000, 0.1);
+        assert!(!game.game_over);
+        assert!(game.health >= INITIAL_HEALTH);
+        assert!(result.score > 0);
+        assert!((result.survival_time - 200.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_constant_difficulty_keeps_spawn_interval_and_speed() {
+        let config = GameConfig { constant_difficulty: true, ..GameConfig::default() };
+        let mut game = Game::with_deck(600.0, 800.0, 0, GameMode::Normal, 2.0, parse_cards(TEST_CARD_DATA_LARGE), config).unwrap();
+        assert!(game.is_constant_difficulty());
+        let (interval, speed) = (game.card_spawn_interval, game.card_speed);
+        assert_eq!(speed, INITIAL_CARD_SPEED * 2.0);
+
+        let result = game.simulate(1.0, 1000, 0.1);
+        assert!(result.score > 20);
+        assert_eq!(game.card_spawn_interval, interval);
+        assert_eq!(game.card_speed, speed);
+        assert_eq!(game.unlock_progress().unlocked, game.card_data.len());
+    }
+
+    #[test]
+    fn test_static_difficulty_survives_restart() {
+        let config: GameConfig = serde_json::from_str(r#"{"static_difficulty": true}"#).unwrap();
+        let mut game = Game::with_deck(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_cards(TEST_CARD_DATA_LARGE), config).unwrap();
+        game.restart();
+        assert!(game.is_constant_difficulty());
+
+        game.simulate(1.0, 1000, 0.1);
+        assert!(game.score > 20);
+        assert_eq!(game.card_spawn_interval, INITIAL_SPAWN_INTERVAL);
+        assert_eq!(game.card_speed, INITIAL_CARD_SPEED);
+    }
+
+    #[test]
+    fn test_simulated_game_can_be_replayed() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 4, GameMode::Both, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        let result = game.simulate(0.5, 500, 0.1);
+        assert!(result.score > 0);
+
+        let replayed = Game::replay_with_deck(4, GameMode::Both, parse_cards(TEST_CARD_DATA_LARGE), &game.export_replay()).unwrap();
+        let cards = |g: &Game| g.cards.iter().map(|c| (c.id, c.front.clone(), c.x)).collect::<Vec<_>>();
+        assert_eq!(replayed.score, game.score);
+        assert_eq!(cards(&replayed), cards(&game));
+        assert_eq!(replayed.rng_word_pos(), game.rng_word_pos());
+    }
+
+    #[test]
+    fn test_simulate_without_answers_ends_the_game() {
+        let mut game = Game::from_deck_text(600.0, 800.0, 0, GameMode::Normal, 1.0, TEST_CARD_DATA_LARGE).unwrap();
+        let result = game.simulate(0.0, 10_000, 0.1);
+        assert!(game.game_over);
+        assert_eq!(result.score, 0);
+        assert!(result.survival_time < 1000.0);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_give_up_reveals_cards_and_ends_the_game() {
+        let mut game = new_game_for_test(600.0, 100_000.0, 0, GameMode::Normal, 1.0);
+        game.spawn_card();
+        let fronts: Vec<String> = game.cards.iter().map(|card| card.raw_front.clone()).collect();
+        assert_eq!(fronts.len(), 2);
+
+        game.give_up();
+        assert!(game.is_game_over());
+        assert_eq!(game.get_health(), INITIAL_HEALTH);
+        assert!(game.cards.iter().all(|card| card.flipped));
+        let missed: Vec<String> = game.missed_cards.iter().map(|card| card.raw_front.clone()).collect();
+        assert_eq!(missed, fronts);
+        for front in &fronts {
+            assert!(game.card_miss_counts[front] >= 1);
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn test_next_card_preview_matches_the_next_spawn() {
+        let mut game = new_game_for_test(600.0, 100_000.0, 0, GameMode::Normal, 1.0);
+        while !game.card_deck.is_empty() {
+            let rng_position = game.rng.get_word_pos();
+            let deck_len = game.card_deck.len();
+            let preview: serde_json::Value = serde_wasm_bindgen::from_value(game.get_next_card_preview()).unwrap();
+            assert_eq!(game.rng.get_word_pos(), rng_position);
+            assert_eq!(game.card_deck.len(), deck_len);
+
+            game.spawn_card();
+            let spawned = game.cards.last().unwrap();
+            assert_eq!(preview["raw_front"], spawned.raw_front.as_str());
+            assert_eq!(preview["raw_back"], spawned.raw_back.as_str());
+        }
+
+        game.card_deck.clear();
+        assert!(game.get_next_card_preview().is_null());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_long_fronts_get_wider_cards_that_stay_on_screen() {
+        let deck = parse_deck("A front that is far too long for a normal card\tanswer\nShort\tkurz");
+        let mut game = Game::new(400.0, 800.0, 0, GameMode::Normal, 1.0, deck, JsValue::UNDEFINED).unwrap();
+        for _ in 0..20 {
+            game.cards.clear();
+            game.spawn_card();
+            let card = &game.cards[0];
+            if card.front == "Short" {
+                assert_eq!(card.width, CARD_WIDTH);
+            } else {
+                assert!(card.width > CARD_WIDTH);
+                assert!(card.width <= MAX_CARD_WIDTH);
+            }
+            assert!(card.x >= 0.0 && card.x + card.width <= 400.0);
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn test_taller_cards_flip_higher() {
+        let height = 800.0;
+        let config = GameConfig { card_width: Some(200.0), card_height: Some(120.0), ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, height, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        game.card_spawn_interval = 1_000_000.0;
+        assert_eq!(game.get_card_height(), 120.0);
+        assert_eq!(game.cards[0].width, 200.0);
+        assert!(game.cards[0].x + 200.0 <= 600.0);
+
+        let flip_y = height - 120.0;
+        game.tick(flip_y / game.card_speed - 0.1);
+        assert!(!game.cards[0].flipped);
+        game.tick(0.2);
+        assert!(game.cards[0].flipped);
+        assert_eq!(game.cards[0].y, flip_y);
+        assert!(flip_y < height - CARD_HEIGHT);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_slowmo_slows_cards_for_its_duration() {
+        let mut game = new_game_for_test(600.0, 100_000.0, 0, GameMode::Normal, 1.0);
+        game.card_spawn_interval = 1_000_000.0;
+        let speed = game.card_speed;
+        game.activate_slowmo(1.0);
+        assert!(game.is_slowmo_active());
+
+        game.tick(0.5);
+        assert_eq!(game.cards[0].y, speed * SLOWMO_SPEED_FACTOR * 0.5);
+        game.activate_slowmo(1.0);
+        game.tick(0.5);
+        assert!(game.is_slowmo_active());
+        game.tick(0.5);
+        assert!(!game.is_slowmo_active());
+
+        let y = game.cards[0].y;
+        game.tick(0.5);
+        assert_eq!(game.cards[0].y - y, speed * 0.5);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_without_autostart_the_board_starts_empty() {
+        let config = GameConfig { autostart: Some(false), ..GameConfig::default() };
+        let config_jsvalue = serde_wasm_bindgen::to_value(&config).unwrap();
+        let mut game = Game::new(600.0, 800.0, 0, GameMode::Normal, 1.0, parse_deck(TEST_CARD_DATA), config_jsvalue).unwrap();
+        let cards: Vec<Card> = serde_wasm_bindgen::from_value(game.get_cards()).unwrap();
+        assert!(cards.is_empty());
+
+        game.tick(0.01);
+        assert_eq!(game.cards.len(), 1);
+
+        game.restart();
+        assert!(game.cards.is_empty());
+    }
 }
@@ -9,7 +9,7 @@ if (window.isFlashCardGameRunning) {
         import('./cards.js'),
     ]).then(async ([module, wasm, cards]) => {
     await module.default(wasm.default);
-    const { Game, GameMode, parse_deck, configure_deck } = module;
+    const { Game, GameMode, parse_deck, parse_deck_json, validate_deck, configure_deck } = module;
     const { CARD_DATA: defaultCardDataString } = cards;
 
     const startScreen = document.getElementById('start-screen');
@@ -65,7 +65,7 @@ if (window.isFlashCardGameRunning) {
     const seed = BigInt(Math.floor(Math.random() * 2**32));
     let game;
     try {
-        game = Game.new(GAME_WIDTH, GAME_HEIGHT, seed, mode, speedMultiplier, configuredDeck);
+        game = Game.new(GAME_WIDTH, GAME_HEIGHT, seed, mode, speedMultiplier, configuredDeck, {});
     } catch (e) {
         alert(`Error initializing game: ${e}`);
         startScreen.classList.remove('hidden');
@@ -207,13 +207,23 @@ if (window.isFlashCardGameRunning) {
 
         game.tick(deltaTime || 0);
 
+        for (const event of game.drain_events()) {
+            if (event.type === 'HeartLost') {
+                healthElement.classList.add('shake');
+                setTimeout(() => {
+                    healthElement.classList.remove('shake');
+                }, 500);
+            }
+        }
+
         render(timestamp);
 
         animationFrameId = requestAnimationFrame(gameLoop);
     }
 
     function render(timestamp) {
-        const currentCards = game.get_cards_for_render();
+        const currentCards = game.get_cards_for_render(true);
+        const cardHeight = game.get_card_height();
         const currentCardIds = new Set(currentCards.map(c => c.id));
 
         // Animate and remove solved cards
@@ -306,7 +316,8 @@ if (window.isFlashCardGameRunning) {
             }
             
             // Update common properties
-            if (card.flipped) {
+            // New cards briefly show their back as a preview
+            if (card.flipped || card.show_answer) {
                 cardElement.classList.add('flipped');
             } else {
                 cardElement.classList.remove('flipped');
@@ -317,6 +328,8 @@ if (window.isFlashCardGameRunning) {
                 cardElement.classList.remove('new');
             }
             cardElement.style.left = `${card.x}px`;
+            cardElement.style.width = `${card.width}px`;
+            cardElement.style.height = `${cardHeight}px`;
             cardElement.style.top = `${card.y}px`;
             
             // Use cached references instead of querySelector
@@ -324,11 +337,12 @@ if (window.isFlashCardGameRunning) {
             cardElement.textSpans.back.textContent = card.back;
         }
 
-        scoreElement.textContent = `Score: ${game.get_score()}`;
+        const state = game.get_game_state();
+        scoreElement.textContent = `Score: ${state.score}`;
 
         // Render health
-        const health = game.get_health();
-        const maxHealth = game.get_max_health();
+        const health = state.health;
+        const maxHealth = state.max_health;
         let hearts = '';
         for (let i = 0; i < health; i++) {
             hearts += '❤️';
@@ -339,7 +353,7 @@ if (window.isFlashCardGameRunning) {
         healthElement.innerHTML = hearts;
 
         // Game over
-        if (game.is_game_over()) {
+        if (state.game_over) {
             gameOverScreen.classList.remove('hidden');
             const missed_cards = game.get_missed_cards();
             if (missed_cards.length > 0) {
@@ -350,7 +364,7 @@ if (window.isFlashCardGameRunning) {
         }
 
         // Pause
-        if (game.is_paused()) {
+        if (state.paused) {
             pauseScreen.style.display = 'flex';
         } else {
             pauseScreen.style.display = 'none';
@@ -461,12 +475,25 @@ if (window.isFlashCardGameRunning) {
         const reader = new FileReader();
         reader.onload = (e) => {
             const text = e.target.result;
-            const deck = parse_deck(text);
+            const isJson = file.name.toLowerCase().endsWith('.json');
+            let deck;
+            try {
+                deck = isJson ? parse_deck_json(text) : parse_deck(text);
+            } catch (error) {
+                alert(error);
+                ankiImportInput.value = '';
+                return;
+            }
 
             if (deck.length > 0) {
+                const { duplicates } = isJson ? { duplicates: [] } : validate_deck(text);
+                if (duplicates.length > 0) {
+                    const lines = duplicates.map(d => `"${d.front}" (lines ${d.lines.join(', ')})`);
+                    alert(`Some cards appear more than once and will share statistics:\n${lines.join('\n')}`);
+                }
                 showDeckConfiguration(deck);
             } else {
-                alert('Could not parse deck. Make sure it is a tab-separated .txt file with "front\tback" format.');
+                alert('Could not parse deck. Make sure it is a tab- or comma-separated file with "front\tback" format.');
                 ankiImportInput.value = '';
             }
         };